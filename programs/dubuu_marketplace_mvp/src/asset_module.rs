@@ -2,10 +2,14 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::hash; // For hashing asset_id_seed_str
 use crate::state::*; // Assumes AssetAccount, OwnershipStatus, AssetListedStatus, MarketplaceConfig are here
 use crate::errors::*; // Assumes DubuuMarketplaceError is here
+use crate::config_module;
 
 // Define the maximum length for the CID string
 // Adjust this value based on the typical length of your Walrus CIDs (e.g., IPFS v0 or v1)
 const MAX_METADATA_CID_LENGTH: usize = 100; // Example: IPFS CIDs are typically around 46-59 chars for v0/v1
+pub const ASSET_ACCOUNT_SPACE: usize = 8 + 32 + 32 + 32 + (4 + MAX_METADATA_CID_LENGTH) + 1 + 1 + (1 + 32) + 1 + 8;
+// Assets registered before auction_count was added.
+pub const LEGACY_ASSET_ACCOUNT_SPACE: usize = ASSET_ACCOUNT_SPACE - 8;
 
 // --- Account Context Structs for Instructions ---
 
@@ -15,7 +19,7 @@ pub struct RegisterAssetAccounts<'info> {
     #[account(
         init,
         payer = signer,
-        space = 8 + 32 + 32 + 32 + (4 + MAX_METADATA_CID_LENGTH) + 1 + 1 + (1 + 32) + 1,
+        space = ASSET_ACCOUNT_SPACE,
         seeds = [
             b"asset",
             &hash::hash(asset_id_seed_str.as_bytes()).to_bytes()[..5] // Take first 5 bytes
@@ -32,19 +36,24 @@ pub struct RegisterAssetAccounts<'info> {
 
 #[derive(Accounts)]
 pub struct AdminVerifyOwnershipAccounts<'info> {
-    #[account(mut)]
+    // Re-verifying a listed asset would leave its auction or fixed-price listing pointing at a changed asset.
+    #[account(
+        mut,
+        constraint = asset_account.asset_listed_status != AssetListedStatus::InAuction &&
+            asset_account.asset_listed_status != AssetListedStatus::ListedFixedPrice @ DubuuMarketplaceError::InvalidAssetStatus
+    )]
     pub asset_account: Account<'info, AssetAccount>,
 
     #[account(
-        seeds = [b"marketplace_config"], // Assuming MarketplaceConfig is a singleton PDA
-        bump = marketplace_config.bump
+        seeds = [b"role_registry".as_ref()],
+        bump = role_registry.bump
     )]
-    pub marketplace_config: Account<'info, MarketplaceConfig>,
+    pub role_registry: Account<'info, RoleRegistry>,
 
     #[account(
-        constraint = admin.key() == marketplace_config.admin @ DubuuMarketplaceError::Unauthorized
+        constraint = config_module::internal_has_role(&role_registry, MarketplaceRole::Verifier, verifier.key()) @ DubuuMarketplaceError::Unauthorized
     )]
-    pub admin: Signer<'info>,
+    pub verifier: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -58,6 +67,19 @@ pub struct UpdateAssetMetadataAccounts<'info> {
     pub current_owner: Signer<'info>, // The signer must be the current_owner
 }
 
+// Grows an asset registered before auction_count existed. Anyone may pay for it.
+#[derive(Accounts)]
+pub struct MigrateAssetAccounts<'info> {
+    /// CHECK: Still in the legacy layout, so it cannot load as Account<AssetAccount> yet. The handler checks the size and deserializes it as an AssetAccount once grown.
+    #[account(mut, owner = crate::ID @ DubuuMarketplaceError::InvalidAssetAccount)]
+    pub asset_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 // REMOVED or REPURPOSED: This context might not be directly used by the refactored internal functions.
 // If other true "internal instructions" within this module need a similar restricted context,
 // you could define a new one. For now, the refactored functions below take direct account refs.
@@ -96,6 +118,7 @@ pub fn handle_register_asset_and_submit_docs_ref(
     asset_account.asset_listed_status = AssetListedStatus::AwaitingOwnershipVerification;
     asset_account.active_auction_key = None;
     asset_account.bump = bump;
+    asset_account.auction_count = 0;
 
     // Optionally emit an event for asset registration
     // emit!(AssetRegistered { asset_key: asset_account.key(), creator: asset_account.creator });
@@ -144,6 +167,7 @@ pub fn handle_update_asset_walrus_cid(
     // Ensure asset is not in an active auction or already sold
     require!(
         asset_account.asset_listed_status != AssetListedStatus::InAuction &&
+        asset_account.asset_listed_status != AssetListedStatus::ListedFixedPrice &&
         asset_account.asset_listed_status != AssetListedStatus::Sold,
         DubuuMarketplaceError::AssetStatusPreventsUpdate
     );
@@ -159,6 +183,25 @@ pub fn handle_update_asset_walrus_cid(
     Ok(())
 }
 
+pub fn handle_migrate_asset(ctx: Context<MigrateAssetAccounts>) -> Result<()> {
+    let asset_info = &ctx.accounts.asset_account;
+    require!(
+        asset_info.data_len() == LEGACY_ASSET_ACCOUNT_SPACE,
+        DubuuMarketplaceError::AccountNotMigratable
+    );
+
+    // The appended bytes are zeroed, so auction_count starts at 0. Legacy auctions were seeded
+    // without a nonce, so the first new listing cannot collide with them.
+    config_module::internal_grow_account(
+        asset_info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        ASSET_ACCOUNT_SPACE,
+    )?;
+    AssetAccount::try_deserialize(&mut &asset_info.try_borrow_data()?[..])?;
+
+    Ok(())
+}
 
 // --- Public Helper Functions (called by other modules within the same program, NOT direct instructions) ---
 
@@ -193,8 +236,10 @@ pub fn internal_update_asset_status_to_in_auction<'info>(
     // This function assumes that the calling function (e.g., list_asset_for_auction)
     // has already verified that the asset *can* be moved to 'InAuction' state.
 
+    // A Sold asset can be relisted by its new owner.
     require!(
-        asset_account.asset_listed_status == AssetListedStatus::ReadyForAuction,
+        asset_account.asset_listed_status == AssetListedStatus::ReadyForAuction ||
+        asset_account.asset_listed_status == AssetListedStatus::Sold,
         DubuuMarketplaceError::AssetNotReadyForAuction
     );
     require!(
@@ -204,6 +249,9 @@ pub fn internal_update_asset_status_to_in_auction<'info>(
 
     asset_account.asset_listed_status = AssetListedStatus::InAuction;
     asset_account.active_auction_key = Some(auction_key);
+    asset_account.auction_count = asset_account.auction_count
+        .checked_add(1)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
 
     // Optionally emit an event
     // emit!(AssetListedForAuction { asset_key: asset_account.key(), auction_key: auction_key });
@@ -211,6 +259,75 @@ pub fn internal_update_asset_status_to_in_auction<'info>(
     Ok(())
 }
 
+// Called when an auction ends without a sale so the owner can list the asset again.
+pub fn internal_return_asset_to_ready_for_auction<'info>(
+    asset_account: &mut Account<'info, AssetAccount>,
+    auction_key: Pubkey,
+) -> Result<()> {
+    require!(
+        asset_account.asset_listed_status == AssetListedStatus::InAuction,
+        DubuuMarketplaceError::InvalidAssetStatus
+    );
+    require!(
+        asset_account.active_auction_key == Some(auction_key),
+        DubuuMarketplaceError::InvalidAssetAccount
+    );
+
+    asset_account.asset_listed_status = AssetListedStatus::ReadyForAuction;
+    asset_account.active_auction_key = None;
+
+    Ok(())
+}
+
+// Fixed-price counterpart of internal_update_asset_status_to_in_auction.
+pub fn internal_update_asset_status_to_listed_fixed_price<'info>(
+    asset_account: &mut Account<'info, AssetAccount>,
+) -> Result<()> {
+    require!(
+        asset_account.asset_listed_status == AssetListedStatus::ReadyForAuction ||
+        asset_account.asset_listed_status == AssetListedStatus::Sold,
+        DubuuMarketplaceError::AssetNotReadyForAuction
+    );
+    require!(
+        asset_account.ownership_verification_status == OwnershipStatus::Verified,
+        DubuuMarketplaceError::OwnershipVerificationRequired
+    );
+
+    asset_account.asset_listed_status = AssetListedStatus::ListedFixedPrice;
+
+    Ok(())
+}
+
+// Called when a fixed-price listing is withdrawn so the owner can list the asset again.
+pub fn internal_return_asset_from_fixed_price_listing<'info>(
+    asset_account: &mut Account<'info, AssetAccount>,
+) -> Result<()> {
+    require!(
+        asset_account.asset_listed_status == AssetListedStatus::ListedFixedPrice,
+        DubuuMarketplaceError::InvalidAssetStatus
+    );
+
+    asset_account.asset_listed_status = AssetListedStatus::ReadyForAuction;
+
+    Ok(())
+}
+
+// Sends an asset back to the verification queue, e.g. after an admin cancels its auction for suspected fraud.
+pub fn internal_flag_asset_for_review<'info>(
+    asset_account: &mut Account<'info, AssetAccount>,
+) -> Result<()> {
+    asset_account.ownership_verification_status = OwnershipStatus::PendingReview;
+    asset_account.asset_listed_status = AssetListedStatus::AwaitingOwnershipVerification;
+
+    emit!(OwnershipVerificationUpdatedEvent {
+        asset_key: asset_account.key(),
+        status: OwnershipStatus::PendingReview,
+        notes_hash: None,
+    });
+
+    Ok(())
+}
+
 // Ensure your state.rs or this file defines these:
 // #[account] pub struct AssetAccount { ... }
 // pub enum OwnershipStatus { ... }
@@ -4,6 +4,7 @@ use anchor_spl::token::{self, TokenAccount, Mint, Transfer, CloseAccount};
 use crate::state::*; // This will bring in BalanceAttestationPayload with the correct field name
 use crate::errors::*;
 use crate::asset_module;
+use crate::config_module;
 
 // ASSUMED UPDATED SIGNATURES in asset_module.rs for direct calls:
 // pub fn internal_update_asset_status_to_in_auction<'info>(
@@ -39,8 +40,8 @@ pub struct ListAssetForAuctionAccounts<'info> {
     #[account(
         init,
         payer = seller,
-        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 33 + 1 + 1 + 1,
-        seeds = [b"auction".as_ref(), asset_account.key().as_ref()],
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 33 + 1 + 1 + 1 + 9 + 1 + 8 + 8 + 8 + 2 + 9 + 8 + 8 + 8 + 1 + 8 + 8 + 4 + 8 + 8 + 2 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 2,
+        seeds = [b"auction".as_ref(), asset_account.key().as_ref(), &asset_account.auction_count.to_le_bytes()],
         bump
     )]
     pub auction_account: Account<'info, AuctionAccount>,
@@ -49,7 +50,8 @@ pub struct ListAssetForAuctionAccounts<'info> {
         mut,
         constraint = asset_account.current_owner == seller.key() @ DubuuMarketplaceError::Unauthorized,
         constraint = asset_account.ownership_verification_status == OwnershipStatus::Verified @ DubuuMarketplaceError::OwnershipVerificationRequired,
-        constraint = asset_account.asset_listed_status == AssetListedStatus::ReadyForAuction @ DubuuMarketplaceError::AssetNotReadyForAuction
+        constraint = asset_account.asset_listed_status == AssetListedStatus::ReadyForAuction ||
+            asset_account.asset_listed_status == AssetListedStatus::Sold @ DubuuMarketplaceError::AssetNotReadyForAuction
     )]
     pub asset_account: Account<'info, AssetAccount>,
     
@@ -70,13 +72,6 @@ pub struct ListAssetForAuctionAccounts<'info> {
     )]
     pub seller_pern_token_account: Account<'info, TokenAccount>,
     
-    #[account(
-        mut,
-        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
-        constraint = treasury_pern_token_account.mint == marketplace_config.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
-    )]
-    pub treasury_pern_token_account: Account<'info, TokenAccount>,
-    
     #[account(
         init,
         payer = seller,
@@ -86,6 +81,17 @@ pub struct ListAssetForAuctionAccounts<'info> {
         bump
     )]
     pub auction_escrow_token_account: Account<'info, TokenAccount>,
+
+    // Holds the listing fee until the auction can no longer be cancelled, so a refund needs no treasury signature.
+    #[account(
+        init,
+        payer = seller,
+        token::mint = pern_usd_star_mint_account,
+        token::authority = auction_escrow_authority,
+        seeds = [b"listing_fee_vault".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub listing_fee_vault: Account<'info, TokenAccount>,
     
     #[account(
         address = marketplace_config.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
@@ -103,13 +109,28 @@ pub struct ListAssetForAuctionAccounts<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ActivateAuctionAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_status == AuctionProcessStatus::PendingStart @ DubuuMarketplaceError::InvalidAuctionStatus
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    pub signer: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct PlaceBidAccounts<'info> {
     #[account(
         mut,
-        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref()],
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
         bump = auction_account.bump,
-        constraint = auction_account.auction_status == AuctionProcessStatus::Active @ DubuuMarketplaceError::AuctionNotInActiveState
+        constraint = auction_account.auction_type == AuctionType::English @ DubuuMarketplaceError::InvalidAuctionType,
+        constraint = auction_account.auction_status == AuctionProcessStatus::Active ||
+            auction_account.auction_status == AuctionProcessStatus::PendingStart @ DubuuMarketplaceError::AuctionNotInActiveState
     )]
     pub auction_account: Account<'info, AuctionAccount>,
     
@@ -136,10 +157,111 @@ pub struct PlaceBidAccounts<'info> {
         bump = auction_account.escrow_authority_bump
     )]
     pub auction_escrow_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + 32 + 32 + 32 + 8 + (1 + 8) + 1 + 8 + (4 + MAX_BID_HISTORY_ENTRIES * (8 + 8)),
+        seeds = [b"bid_record".as_ref(), auction_account.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_record: Account<'info, BidRecord>,
     
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuyDutchAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_type == AuctionType::Dutch @ DubuuMarketplaceError::InvalidAuctionType,
+        constraint = auction_account.auction_status == AuctionProcessStatus::Active ||
+            auction_account.auction_status == AuctionProcessStatus::PendingStart @ DubuuMarketplaceError::AuctionNotInActiveState
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
     #[account(mut)]
-    pub previous_highest_bidder_token_account: Option<Account<'info, TokenAccount>>,
-    
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = buyer_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = buyer_pern_token_account.owner == buyer.key() @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub buyer_pern_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + 32 + 32 + 32 + 8 + (1 + 8) + 1 + 8 + (4 + MAX_BID_HISTORY_ENTRIES * (8 + 8)),
+        seeds = [b"bid_record".as_ref(), auction_account.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub bid_record: Account<'info, BidRecord>,
+
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawOutbidFundsAccounts<'info> {
+    #[account(
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_type != AuctionType::SealedBid @ DubuuMarketplaceError::InvalidAuctionType
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"bid_record".as_ref(), auction_account.key().as_ref(), bid_record.bidder.as_ref()],
+        bump = bid_record.bump,
+        constraint = bid_record.auction_key == auction_account.key() @ DubuuMarketplaceError::InvalidBidRecord
+    )]
+    pub bid_record: Account<'info, BidRecord>,
+
+    /// CHECK: Owner of the bid record; receives the record's rent once the auction is over. Anyone may crank the withdrawal.
+    #[account(mut, address = bid_record.bidder @ DubuuMarketplaceError::Unauthorized)]
+    pub bidder: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bidder_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = bidder_pern_token_account.owner == bid_record.bidder @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub bidder_pern_token_account: Account<'info, TokenAccount>,
+
+    // Omitted once the escrow has been closed and the record holds nothing.
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA authority for the escrow account's tokens.
+    #[account(
+        seeds = [b"escrow_authority".as_ref(), auction_account.key().as_ref()],
+        bump = auction_account.escrow_authority_bump
+    )]
+    pub auction_escrow_authority: AccountInfo<'info>,
+
+    /// CHECK: Seller's account to return the escrow rent to once the last bid is withdrawn.
+    #[account(mut, address = auction_account.seller @ DubuuMarketplaceError::InvalidSellerAccountForRent)]
+    pub seller_rent_recipient: AccountInfo<'info>,
+
     pub token_program: Program<'info, token::Token>,
 }
 
@@ -147,13 +269,19 @@ pub struct PlaceBidAccounts<'info> {
 pub struct FinalizeAuctionAccounts<'info> {
     #[account(
         mut,
-        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref()],
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
         bump = auction_account.bump
     )]
     pub auction_account: Account<'info, AuctionAccount>,
 
     pub signer: Signer<'info>, 
 
+    #[account(
+        mut,
+        constraint = asset_account.key() == auction_account.asset_key @ DubuuMarketplaceError::InvalidAssetAccount
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
     #[account(
         mut,
         seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
@@ -168,10 +296,7 @@ pub struct FinalizeAuctionAccounts<'info> {
     )]
     pub auction_escrow_authority: AccountInfo<'info>,
     
-    #[account(mut)]
-    pub highest_bidder_token_account_for_refund: Option<Account<'info, TokenAccount>>, 
-    
-    /// CHECK: Seller's account to return rent to when closing escrow if unsold with no bids.
+    /// CHECK: Seller's account to return rent to when closing escrow if unsold or the reserve was not met.
     #[account(mut, address = auction_account.seller @ DubuuMarketplaceError::InvalidSellerAccountForRent)]
     pub seller_rent_recipient: AccountInfo<'info>,
 
@@ -179,236 +304,1424 @@ pub struct FinalizeAuctionAccounts<'info> {
     pub system_program: Program<'info, System>,
 }
 
-
 #[derive(Accounts)]
-pub struct SettleAuctionAccounts<'info> {
+pub struct CancelAuctionAccounts<'info> {
     #[account(
         mut,
-        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref()],
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
         bump = auction_account.bump,
-        constraint = auction_account.auction_status == AuctionProcessStatus::EndedSoldPayPending @ DubuuMarketplaceError::AuctionNotInSettlementState,
-        constraint = auction_account.highest_bidder.is_some() && auction_account.highest_bidder.unwrap() == highest_bidder.key() @ DubuuMarketplaceError::NotAuctionWinner
+        has_one = seller @ DubuuMarketplaceError::Unauthorized,
+        constraint = auction_account.auction_status == AuctionProcessStatus::Active ||
+            auction_account.auction_status == AuctionProcessStatus::PendingStart @ DubuuMarketplaceError::AuctionNotInActiveState,
+        constraint = auction_account.bid_count == 0 @ DubuuMarketplaceError::AuctionHasBids,
+        close = seller
     )]
     pub auction_account: Account<'info, AuctionAccount>,
-    
+
     #[account(mut)]
-    pub highest_bidder: Signer<'info>,
-    
+    pub seller: Signer<'info>,
+
     #[account(
         mut,
         constraint = asset_account.key() == auction_account.asset_key @ DubuuMarketplaceError::InvalidAssetAccount
     )]
     pub asset_account: Account<'info, AssetAccount>,
-    
+
     #[account(
         seeds = [b"marketplace_config".as_ref()],
         bump = marketplace_config.bump
     )]
     pub marketplace_config: Account<'info, MarketplaceConfig>,
-    
+
     #[account(
         mut,
         seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
         bump
     )]
     pub auction_escrow_token_account: Account<'info, TokenAccount>,
-    
+
     /// CHECK: PDA authority for the escrow account's tokens.
     #[account(
         seeds = [b"escrow_authority".as_ref(), auction_account.key().as_ref()],
         bump = auction_account.escrow_authority_bump
     )]
     pub auction_escrow_authority: AccountInfo<'info>,
-    
+
     #[account(
         mut,
-        constraint = seller_token_account.owner == auction_account.seller @ DubuuMarketplaceError::InvalidTokenAccountOwner,
-        constraint = seller_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+        seeds = [b"listing_fee_vault".as_ref(), auction_account.key().as_ref()],
+        bump
     )]
-    pub seller_token_account: Account<'info, TokenAccount>,
-    
+    pub listing_fee_vault: Account<'info, TokenAccount>,
+
+    // Receives the listing fee back when the marketplace refunds it on cancellation.
     #[account(
         mut,
-        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
-        constraint = treasury_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+        constraint = seller_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = seller_pern_token_account.owner == seller.key() @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub seller_pern_token_account: Account<'info, TokenAccount>,
+
+    // Receives the listing fee when it is not refunded.
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount
     )]
     pub treasury_pern_token_account: Account<'info, TokenAccount>,
-    
+
     pub token_program: Program<'info, token::Token>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(payload_argument: BalanceAttestationPayload)] // This makes payload_argument available to seeds/constraints
-pub struct ProcessWormholeAttestationAccounts<'info> {
+pub struct CollectListingFeeAccounts<'info> {
+    // The fee is earned once neither the seller nor admin_cancel_auction can unwind the auction any more.
     #[account(
-        init_if_needed,
-        payer = relayer,
-        space = 8 + 32 + 2 + 32 + 8 + 8 + 1, 
-        seeds = [
-            b"attestation".as_ref(), 
-            payload_argument.solana_target_address.as_ref(), 
-            &payload_argument.evm_chain_id.to_le_bytes(),
-            // This uses the field from the #[instruction] argument
-            &anchor_lang::solana_program::hash::hash(&payload_argument.asset_address_on_evm_as_bytes).to_bytes()
-        ],
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_status != AuctionProcessStatus::Active &&
+            auction_account.auction_status != AuctionProcessStatus::PendingStart &&
+            auction_account.auction_status != AuctionProcessStatus::EndedSoldPayPending @ DubuuMarketplaceError::ListingFeeNotCollectable
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"listing_fee_vault".as_ref(), auction_account.key().as_ref()],
         bump
     )]
-    pub cross_chain_attestation: Account<'info, CrossChainAttestation>,
-    
+    pub listing_fee_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA authority for the listing fee vault's tokens.
     #[account(
-        seeds = [b"wormhole_listener".as_ref()],
-        bump = wormhole_listener_config.bump,
-        constraint = relayer.key() == wormhole_listener_config.wormhole_authorized_relayer @ DubuuMarketplaceError::Unauthorized
+        seeds = [b"escrow_authority".as_ref(), auction_account.key().as_ref()],
+        bump = auction_account.escrow_authority_bump
     )]
-    pub wormhole_listener_config: Account<'info, WormholeListenerConfig>,
-    
-    #[account(mut)]
-    pub relayer: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub auction_escrow_authority: AccountInfo<'info>,
 
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
 
-// --- Instruction Handlers ---
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
 
-pub fn handle_initialize_wormhole_listener(
-    ctx: Context<InitializeWormholeListenerAccounts>,
-    authorized_relayer: Pubkey,
-) -> Result<()> {
-    let wormhole_listener_config = &mut ctx.accounts.wormhole_listener_config;
-    wormhole_listener_config.wormhole_authorized_relayer = authorized_relayer;
-    wormhole_listener_config.bump = ctx.bumps.wormhole_listener_config;
-    
-    Ok(())
-}
+    /// CHECK: Seller's account to return the vault rent to.
+    #[account(mut, address = auction_account.seller @ DubuuMarketplaceError::InvalidSellerAccountForRent)]
+    pub seller_rent_recipient: AccountInfo<'info>,
 
-pub fn handle_list_asset_for_auction(
-    ctx: Context<ListAssetForAuctionAccounts>,
-    start_price_usd_star: u64,
-    duration_seconds: i64,
-) -> Result<()> {
-    let auction_account = &mut ctx.accounts.auction_account;
-    let marketplace_config = &ctx.accounts.marketplace_config;
-    let asset_account = &mut ctx.accounts.asset_account;
+    pub signer: Signer<'info>,
 
-    let auction_account_bump = ctx.bumps.auction_account;
-    let auction_escrow_authority_bump = ctx.bumps.auction_escrow_authority;
-    
-    let cpi_accounts_fee = Transfer {
-        from: ctx.accounts.seller_pern_token_account.to_account_info(),
-        to: ctx.accounts.treasury_pern_token_account.to_account_info(),
-        authority: ctx.accounts.seller.to_account_info(),
-    };
-    let cpi_ctx_fee = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts_fee,
-    );
-    token::transfer(cpi_ctx_fee, marketplace_config.listing_fee_usd_star)?;
-    
-    let clock = Clock::get()?;
-    let auction_end_timestamp = clock.unix_timestamp.checked_add(duration_seconds)
-        .ok_or_else(|| DubuuMarketplaceError::TimestampOverflow)?;
-    
-    auction_account.asset_key = asset_account.key();
-    auction_account.seller = ctx.accounts.seller.key();
-    auction_account.pern_usd_star_mint = marketplace_config.pern_usd_star_mint;
-    auction_account.start_price_usd_star = start_price_usd_star;
-    auction_account.auction_end_timestamp = auction_end_timestamp;
-    auction_account.highest_bid_usd_star = start_price_usd_star; 
-    auction_account.highest_bidder = None;
-    auction_account.auction_status = AuctionProcessStatus::Active;
-    auction_account.escrow_authority_bump = auction_escrow_authority_bump;
-    auction_account.bump = auction_account_bump;
-    
-    asset_module::internal_update_asset_status_to_in_auction(
-        asset_account,
-        auction_account.key()
-    )?;
-    
-    Ok(())
+    pub token_program: Program<'info, token::Token>,
 }
 
-pub fn handle_place_bid(
-    ctx: Context<PlaceBidAccounts>,
-    bid_amount_usd_star: u64,
-) -> Result<()> {
-    let auction_account = &mut ctx.accounts.auction_account;
-    
-    require!(
-        bid_amount_usd_star > auction_account.highest_bid_usd_star,
-        DubuuMarketplaceError::BidTooLow
-    );
-    
-    let clock = Clock::get()?;
-    require!(
-        clock.unix_timestamp < auction_account.auction_end_timestamp,
-        DubuuMarketplaceError::AuctionEnded
-    );
-    
-    let auction_key_as_bytes = auction_account.key().to_bytes();
-    let escrow_authority_seeds_slices: &[&[u8]] = &[
-        b"escrow_authority".as_ref(),
-        auction_key_as_bytes.as_ref(),
-        &[auction_account.escrow_authority_bump],
-    ];
-    let signer_seeds = &[&escrow_authority_seeds_slices[..]];
-
-    if let Some(previous_highest_bidder_key) = auction_account.highest_bidder {
-        let previous_bidder_token_account_opt = ctx.accounts.previous_highest_bidder_token_account.as_ref();
-        require!(previous_bidder_token_account_opt.is_some(), DubuuMarketplaceError::MissingPreviousBidderAccount);
-        
-        let previous_bidder_token_account = previous_bidder_token_account_opt.unwrap();
-        require_keys_eq!(previous_bidder_token_account.owner, previous_highest_bidder_key, DubuuMarketplaceError::InvalidTokenAccountOwner);
-        require_keys_eq!(previous_bidder_token_account.mint, auction_account.pern_usd_star_mint, DubuuMarketplaceError::InvalidPerenaMint);
-        
-        let cpi_accounts_return = Transfer {
-            from: ctx.accounts.auction_escrow_token_account.to_account_info(),
-            to: previous_bidder_token_account.to_account_info(),
-            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
-        };
-        let cpi_ctx_return = CpiContext::new_with_signer(
+#[derive(Accounts)]
+pub struct AdminCancelAuctionAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_status == AuctionProcessStatus::PendingStart ||
+            auction_account.auction_status == AuctionProcessStatus::Active ||
+            auction_account.auction_status == AuctionProcessStatus::EndedSoldPayPending @ DubuuMarketplaceError::InvalidAuctionStatus
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        seeds = [b"role_registry".as_ref()],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+
+    // Emergency unwinds belong to the Arbiter or the Pauser, not the admin key.
+    #[account(
+        constraint = config_module::internal_has_role(&role_registry, MarketplaceRole::Arbiter, authority.key()) ||
+            config_module::internal_has_role(&role_registry, MarketplaceRole::Pauser, authority.key()) @ DubuuMarketplaceError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = asset_account.key() == auction_account.asset_key @ DubuuMarketplaceError::InvalidAssetAccount
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA authority for the escrow account's tokens.
+    #[account(
+        seeds = [b"escrow_authority".as_ref(), auction_account.key().as_ref()],
+        bump = auction_account.escrow_authority_bump
+    )]
+    pub auction_escrow_authority: AccountInfo<'info>,
+
+    // Still holds the listing fee: collect_listing_fee only sweeps it once the auction is past cancelling.
+    #[account(
+        mut,
+        seeds = [b"listing_fee_vault".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub listing_fee_vault: Account<'info, TokenAccount>,
+
+    // Receives the listing fee back when refund_listing_fee_on_admin_cancel is set.
+    #[account(
+        mut,
+        constraint = seller_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = seller_pern_token_account.owner == auction_account.seller @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub seller_pern_token_account: Account<'info, TokenAccount>,
+
+    // Receives the listing fee when it is forfeited.
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Seller's account to return the escrow and vault rent to.
+    #[account(mut, address = auction_account.seller @ DubuuMarketplaceError::InvalidSellerAccountForRent)]
+    pub seller_rent_recipient: AccountInfo<'info>,
+
+    pub token_program: Program<'info, token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAuctionAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_status == AuctionProcessStatus::EndedSoldPayPending @ DubuuMarketplaceError::AuctionNotInSettlementState,
+        constraint = auction_account.highest_bidder.is_some() && auction_account.highest_bidder.unwrap() == highest_bidder.key() @ DubuuMarketplaceError::NotAuctionWinner
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+    
+    /// CHECK: The auction winner, checked against auction_account.highest_bidder. Only signs before the settlement deadline.
+    #[account(mut)]
+    pub highest_bidder: AccountInfo<'info>,
+
+    // The winner, or after settlement_deadline_timestamp anyone settling on their behalf.
+    pub settler: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = asset_account.key() == auction_account.asset_key @ DubuuMarketplaceError::InvalidAssetAccount
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+    
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: PDA authority for the escrow account's tokens.
+    #[account(
+        seeds = [b"escrow_authority".as_ref(), auction_account.key().as_ref()],
+        bump = auction_account.escrow_authority_bump
+    )]
+    pub auction_escrow_authority: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == auction_account.seller @ DubuuMarketplaceError::InvalidTokenAccountOwner,
+        constraint = seller_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
+        constraint = treasury_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
+
+    // Only required when the winner escrowed more than the clearing price (proxy maximum or second-price bid).
+    #[account(mut)]
+    pub highest_bidder_token_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmDeliveryAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_status == AuctionProcessStatus::AwaitingDelivery @ DubuuMarketplaceError::InvalidAuctionStatus,
+        constraint = auction_account.highest_bidder == Some(buyer.key()) @ DubuuMarketplaceError::NotAuctionWinner
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = asset_account.key() == auction_account.asset_key @ DubuuMarketplaceError::InvalidAssetAccount
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA authority for the escrow account's tokens.
+    #[account(
+        seeds = [b"escrow_authority".as_ref(), auction_account.key().as_ref()],
+        bump = auction_account.escrow_authority_bump
+    )]
+    pub auction_escrow_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == auction_account.seller @ DubuuMarketplaceError::InvalidTokenAccountOwner,
+        constraint = seller_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
+        constraint = treasury_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct CrankFinalizeAndSettleAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_status == AuctionProcessStatus::Active ||
+            auction_account.auction_status == AuctionProcessStatus::PendingStart ||
+            auction_account.auction_status == AuctionProcessStatus::EndedSoldPayPending ||
+            auction_account.auction_status == AuctionProcessStatus::AwaitingDelivery @ DubuuMarketplaceError::InvalidAuctionStatus
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    // Anyone may run the crank; the keeper fee goes to keeper_token_account.
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = keeper_token_account.owner == keeper.key() @ DubuuMarketplaceError::InvalidTokenAccountOwner,
+        constraint = keeper_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub keeper_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = asset_account.key() == auction_account.asset_key @ DubuuMarketplaceError::InvalidAssetAccount
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA authority for the escrow account's tokens.
+    #[account(
+        seeds = [b"escrow_authority".as_ref(), auction_account.key().as_ref()],
+        bump = auction_account.escrow_authority_bump
+    )]
+    pub auction_escrow_authority: AccountInfo<'info>,
+
+    /// CHECK: Seller's account to return rent to when closing escrow if unsold or the reserve was not met.
+    #[account(mut, address = auction_account.seller @ DubuuMarketplaceError::InvalidSellerAccountForRent)]
+    pub seller_rent_recipient: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == auction_account.seller @ DubuuMarketplaceError::InvalidTokenAccountOwner,
+        constraint = seller_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
+        constraint = treasury_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The auction winner, checked against auction_account.highest_bidder when the auction sold.
+    #[account(mut)]
+    pub highest_bidder: Option<AccountInfo<'info>>,
+
+    // Only required when the winner escrowed more than the clearing price.
+    #[account(mut)]
+    pub highest_bidder_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, token::Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(payload_argument: BalanceAttestationPayload)] // This makes payload_argument available to seeds/constraints
+pub struct ProcessWormholeAttestationAccounts<'info> {
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + 32 + 2 + 32 + 8 + 8 + 1, 
+        seeds = [
+            b"attestation".as_ref(), 
+            payload_argument.solana_target_address.as_ref(), 
+            &payload_argument.evm_chain_id.to_le_bytes(),
+            // This uses the field from the #[instruction] argument
+            &anchor_lang::solana_program::hash::hash(&payload_argument.asset_address_on_evm_as_bytes).to_bytes()
+        ],
+        bump
+    )]
+    pub cross_chain_attestation: Account<'info, CrossChainAttestation>,
+    
+    #[account(
+        seeds = [b"wormhole_listener".as_ref()],
+        bump = wormhole_listener_config.bump,
+        constraint = relayer.key() == wormhole_listener_config.wormhole_authorized_relayer @ DubuuMarketplaceError::Unauthorized
+    )]
+    pub wormhole_listener_config: Account<'info, WormholeListenerConfig>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+
+// --- Instruction Handlers ---
+
+pub fn handle_initialize_wormhole_listener(
+    ctx: Context<InitializeWormholeListenerAccounts>,
+    authorized_relayer: Pubkey,
+) -> Result<()> {
+    let wormhole_listener_config = &mut ctx.accounts.wormhole_listener_config;
+    wormhole_listener_config.wormhole_authorized_relayer = authorized_relayer;
+    wormhole_listener_config.bump = ctx.bumps.wormhole_listener_config;
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_list_asset_for_auction(
+    mut ctx: Context<ListAssetForAuctionAccounts>,
+    start_price_usd_star: u64,
+    duration_seconds: i64,
+    reserve_price_usd_star: Option<u64>,
+    is_reserve_price_hidden: bool,
+    min_bid_increment_usd_star: Option<u64>,
+    min_bid_increment_bps: Option<u16>,
+    buy_now_price_usd_star: Option<u64>,
+    start_timestamp: Option<i64>,
+) -> Result<()> {
+    if let Some(reserve_price) = reserve_price_usd_star {
+        require!(
+            reserve_price >= start_price_usd_star,
+            DubuuMarketplaceError::InvalidReservePrice
+        );
+    }
+    if let Some(buy_now_price) = buy_now_price_usd_star {
+        require!(
+            buy_now_price > start_price_usd_star &&
+            buy_now_price >= reserve_price_usd_star.unwrap_or_default(),
+            DubuuMarketplaceError::InvalidBuyNowPrice
+        );
+    }
+    if let Some(increment_bps) = min_bid_increment_bps {
+        require!(increment_bps <= 10000, DubuuMarketplaceError::InvalidBasisPoints);
+    }
+
+    open_auction(&mut ctx, AuctionType::English, start_price_usd_star, duration_seconds, start_timestamp)?;
+
+    let auction_account = &mut ctx.accounts.auction_account;
+    let marketplace_config = &ctx.accounts.marketplace_config;
+
+    auction_account.reserve_price_usd_star = reserve_price_usd_star;
+    auction_account.is_reserve_price_hidden = reserve_price_usd_star.is_some() && is_reserve_price_hidden;
+    auction_account.anti_snipe_window_seconds = marketplace_config.anti_snipe_window_seconds;
+    auction_account.anti_snipe_extension_seconds = marketplace_config.anti_snipe_extension_seconds;
+    // Per-auction increments override the marketplace-wide defaults.
+    auction_account.min_bid_increment_usd_star = min_bid_increment_usd_star
+        .unwrap_or(marketplace_config.min_bid_increment_usd_star);
+    auction_account.min_bid_increment_bps = min_bid_increment_bps
+        .unwrap_or(marketplace_config.min_bid_increment_bps);
+    auction_account.buy_now_price_usd_star = buy_now_price_usd_star;
+    
+    Ok(())
+}
+
+pub fn handle_list_asset_for_dutch_auction(
+    mut ctx: Context<ListAssetForAuctionAccounts>,
+    start_price_usd_star: u64,
+    floor_price_usd_star: u64,
+    duration_seconds: i64,
+    price_step_seconds: i64,
+    start_timestamp: Option<i64>,
+) -> Result<()> {
+    require!(
+        floor_price_usd_star < start_price_usd_star,
+        DubuuMarketplaceError::InvalidDutchPriceRange
+    );
+    require!(
+        price_step_seconds >= 0 && price_step_seconds <= duration_seconds,
+        DubuuMarketplaceError::InvalidDutchPriceStep
+    );
+
+    open_auction(&mut ctx, AuctionType::Dutch, start_price_usd_star, duration_seconds, start_timestamp)?;
+
+    let auction_account = &mut ctx.accounts.auction_account;
+    auction_account.dutch_floor_price_usd_star = floor_price_usd_star;
+    auction_account.dutch_price_step_seconds = price_step_seconds;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_list_asset_for_sealed_auction(
+    mut ctx: Context<ListAssetForAuctionAccounts>,
+    min_bid_usd_star: u64,
+    commit_duration_seconds: i64,
+    reveal_duration_seconds: i64,
+    deposit_usd_star: u64,
+    reserve_price_usd_star: Option<u64>,
+    start_timestamp: Option<i64>,
+    is_second_price: bool,
+) -> Result<()> {
+    require!(deposit_usd_star > 0, DubuuMarketplaceError::InvalidSealedBidDeposit);
+    require!(
+        reveal_duration_seconds > 0 &&
+        reveal_duration_seconds <= ctx.accounts.marketplace_config.max_auction_duration_seconds,
+        DubuuMarketplaceError::InvalidRevealDuration
+    );
+    if let Some(reserve_price) = reserve_price_usd_star {
+        require!(
+            reserve_price >= min_bid_usd_star,
+            DubuuMarketplaceError::InvalidReservePrice
+        );
+    }
+
+    open_auction(&mut ctx, AuctionType::SealedBid, min_bid_usd_star, commit_duration_seconds, start_timestamp)?;
+
+    let auction_account = &mut ctx.accounts.auction_account;
+    let marketplace_config = &ctx.accounts.marketplace_config;
+
+    auction_account.reserve_price_usd_star = reserve_price_usd_star;
+    auction_account.reveal_end_timestamp = auction_account.auction_end_timestamp
+        .checked_add(reveal_duration_seconds)
+        .ok_or(DubuuMarketplaceError::TimestampOverflow)?;
+    auction_account.sealed_bid_deposit_usd_star = deposit_usd_star;
+    auction_account.sealed_bid_slash_bps = marketplace_config.sealed_bid_slash_bps;
+    auction_account.is_second_price = is_second_price;
+
+    Ok(())
+}
+
+pub fn handle_activate_auction(ctx: Context<ActivateAuctionAccounts>) -> Result<()> {
+    let auction_account = &mut ctx.accounts.auction_account;
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= auction_account.auction_start_timestamp,
+        DubuuMarketplaceError::AuctionNotStarted
+    );
+
+    internal_activate_pending_auction(auction_account)
+}
+
+pub fn handle_place_bid(
+    ctx: Context<PlaceBidAccounts>,
+    max_bid_usd_star: u64,
+) -> Result<()> {
+    let auction_account = &mut ctx.accounts.auction_account;
+    let bidder_key = ctx.accounts.bidder.key();
+    let is_leader = auction_account.highest_bidder == Some(bidder_key);
+
+    if is_leader {
+        // The leader may only raise their own maximum; the visible price does not move.
+        require!(
+            max_bid_usd_star > auction_account.highest_max_bid_usd_star,
+            DubuuMarketplaceError::BidTooLow
+        );
+    } else if auction_account.highest_bidder.is_none() {
+        // The opening bid may match the start price, which is also what it is charged.
+        require!(
+            max_bid_usd_star > 0 && max_bid_usd_star >= auction_account.highest_bid_usd_star,
+            DubuuMarketplaceError::BidTooLow
+        );
+    } else {
+        require!(
+            max_bid_usd_star > auction_account.highest_bid_usd_star,
+            DubuuMarketplaceError::BidTooLow
+        );
+    }
+
+    // A maximum at or above the buy-it-now price is charged exactly that price and wins outright.
+    let is_buy_now = auction_account.buy_now_price_usd_star
+        .is_some_and(|buy_now_price| max_bid_usd_star >= buy_now_price);
+    let max_bid_usd_star = if is_buy_now {
+        auction_account.buy_now_price_usd_star.unwrap()
+    } else {
+        max_bid_usd_star
+    };
+
+    if auction_account.highest_bidder.is_some() && !is_leader && !is_buy_now {
+        require!(
+            max_bid_usd_star >= minimum_outbid_amount(auction_account)?,
+            DubuuMarketplaceError::BidIncrementTooLow
+        );
+    }
+    
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= auction_account.auction_start_timestamp,
+        DubuuMarketplaceError::AuctionNotStarted
+    );
+    require!(
+        clock.unix_timestamp < auction_account.auction_end_timestamp,
+        DubuuMarketplaceError::AuctionEnded
+    );
+    if auction_account.auction_status == AuctionProcessStatus::PendingStart {
+        // Nobody cranked activate_auction yet; the first bid opens the auction.
+        internal_activate_pending_auction(auction_account)?;
+    }
+
+    let bid_record = &mut ctx.accounts.bid_record;
+    if bid_record.bidder == Pubkey::default() {
+        bid_record.auction_key = auction_account.key();
+        bid_record.bidder = bidder_key;
+        bid_record.bump = ctx.bumps.bid_record;
+    }
+    bid_record.max_bid_usd_star = max_bid_usd_star;
+    internal_record_bid(bid_record, max_bid_usd_star, clock.unix_timestamp);
+
+    // Work out who leads afterwards and at what price, eBay style: the winner pays one
+    // increment over the runner-up's maximum, capped at their own maximum.
+    let leader_max = auction_account.highest_max_bid_usd_star;
+    let takes_lead = !is_leader && (is_buy_now || max_bid_usd_star > leader_max);
+    let (new_leader_max, new_price) = if is_buy_now {
+        (max_bid_usd_star, max_bid_usd_star)
+    } else if is_leader {
+        (max_bid_usd_star, auction_account.highest_bid_usd_star)
+    } else if takes_lead {
+        let price = if auction_account.highest_bidder.is_some() {
+            proxy_price(auction_account, leader_max, max_bid_usd_star)?
+        } else {
+            auction_account.highest_bid_usd_star
+        };
+        (max_bid_usd_star, price)
+    } else {
+        // The standing leader's proxy answers the challenge; ties go to the earlier bidder.
+        (leader_max, proxy_price(auction_account, max_bid_usd_star, leader_max)?)
+    };
+    // Once the leading maximum covers a reserve, the price jumps to the reserve.
+    let new_price = match auction_account.reserve_price_usd_star {
+        Some(reserve_price) if new_leader_max >= reserve_price => new_price.max(reserve_price),
+        _ => new_price,
+    };
+
+    // An outbid leader keeps their funds in their own BidRecord until withdraw_outbid_funds.
+    // The leader's whole maximum sits in escrow, topped up as it grows.
+    if is_leader || takes_lead {
+        let top_up = max_bid_usd_star
+            .checked_sub(bid_record.escrowed_usd_star)
+            .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+        let cpi_accounts_new_bid = Transfer {
+            from: ctx.accounts.bidder_pern_token_account.to_account_info(),
+            to: ctx.accounts.auction_escrow_token_account.to_account_info(),
+            authority: ctx.accounts.bidder.to_account_info(),
+        };
+        let cpi_ctx_new_bid = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_new_bid,
+        );
+        token::transfer(cpi_ctx_new_bid, top_up)?;
+        bid_record.escrowed_usd_star = max_bid_usd_star;
+        auction_account.highest_bidder = Some(bidder_key);
+    }
+
+    let is_visible_change = takes_lead || new_price != auction_account.highest_bid_usd_star;
+    auction_account.highest_max_bid_usd_star = new_leader_max;
+    auction_account.highest_bid_usd_star = new_price;
+    auction_account.bid_count = auction_account.bid_count
+        .checked_add(1)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    if is_buy_now {
+        // Skip finalize_auction: the sale goes straight to the regular settlement path.
+        auction_account.auction_end_timestamp = clock.unix_timestamp;
+        auction_account.clearing_price_usd_star = new_price;
+        open_settlement_window(auction_account, clock.unix_timestamp)?;
+    }
+
+    // Anti-sniping: a bid that moves the price or the lead inside the closing window pushes
+    // the end time out. A leader quietly raising their own maximum does not.
+    let time_remaining = auction_account.auction_end_timestamp - clock.unix_timestamp;
+    if !is_buy_now && is_visible_change && auction_account.anti_snipe_window_seconds > 0 && time_remaining <= auction_account.anti_snipe_window_seconds {
+        let extended_end_timestamp = clock.unix_timestamp
+            .checked_add(auction_account.anti_snipe_extension_seconds)
+            .ok_or(DubuuMarketplaceError::TimestampOverflow)?;
+        if extended_end_timestamp > auction_account.auction_end_timestamp {
+            auction_account.auction_end_timestamp = extended_end_timestamp;
+        }
+    }
+    
+    emit!(BidPlacedEvent {
+        auction_key: auction_account.key(),
+        bidder: bidder_key,
+        highest_bidder: auction_account.highest_bidder.unwrap(),
+        amount: new_price,
+        auction_end_timestamp: auction_account.auction_end_timestamp,
+        is_proxy_bid: !is_leader && !takes_lead,
+    });
+
+    if is_buy_now {
+        emit!(AuctionBoughtNowEvent {
+            auction_key: auction_account.key(),
+            buyer: bidder_key,
+            price: new_price,
+        });
+    }
+    
+    Ok(())
+}
+
+pub fn handle_buy_dutch(ctx: Context<BuyDutchAccounts>) -> Result<()> {
+    let auction_account = &mut ctx.accounts.auction_account;
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= auction_account.auction_start_timestamp,
+        DubuuMarketplaceError::AuctionNotStarted
+    );
+    require!(
+        clock.unix_timestamp < auction_account.auction_end_timestamp,
+        DubuuMarketplaceError::AuctionEnded
+    );
+    if auction_account.auction_status == AuctionProcessStatus::PendingStart {
+        internal_activate_pending_auction(auction_account)?;
+    }
+
+    let price = current_dutch_price(auction_account, clock.unix_timestamp)?;
+
+    let cpi_accounts_payment = Transfer {
+        from: ctx.accounts.buyer_pern_token_account.to_account_info(),
+        to: ctx.accounts.auction_escrow_token_account.to_account_info(),
+        authority: ctx.accounts.buyer.to_account_info(),
+    };
+    let cpi_ctx_payment = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts_payment,
+    );
+    token::transfer(cpi_ctx_payment, price)?;
+
+    let bid_record = &mut ctx.accounts.bid_record;
+    bid_record.auction_key = auction_account.key();
+    bid_record.bidder = ctx.accounts.buyer.key();
+    bid_record.bump = ctx.bumps.bid_record;
+    bid_record.escrowed_usd_star = price;
+    bid_record.max_bid_usd_star = price;
+    internal_record_bid(bid_record, price, clock.unix_timestamp);
+
+    // The first taker wins outright; settlement runs through settle_auction_and_transfer.
+    auction_account.highest_bidder = Some(ctx.accounts.buyer.key());
+    auction_account.highest_bid_usd_star = price;
+    auction_account.highest_max_bid_usd_star = price;
+    auction_account.clearing_price_usd_star = price;
+    auction_account.bid_count = 1;
+    auction_account.auction_end_timestamp = clock.unix_timestamp;
+    open_settlement_window(auction_account, clock.unix_timestamp)?;
+
+    emit!(DutchAuctionPurchasedEvent {
+        auction_key: auction_account.key(),
+        buyer: ctx.accounts.buyer.key(),
+        price,
+    });
+
+    Ok(())
+}
+
+pub fn handle_withdraw_outbid_funds(ctx: Context<WithdrawOutbidFundsAccounts>) -> Result<()> {
+    let auction_account = &ctx.accounts.auction_account;
+    let bid_record = &mut ctx.accounts.bid_record;
+
+    let is_leader = auction_account.highest_bidder == Some(bid_record.bidder);
+    let is_auction_over = match auction_account.auction_status {
+        // The leader's (or unsettled winner's) funds stay put until the auction resolves.
+        AuctionProcessStatus::PendingStart |
+        AuctionProcessStatus::Active |
+        AuctionProcessStatus::EndedSoldPayPending |
+        AuctionProcessStatus::AwaitingDelivery |
+        AuctionProcessStatus::Disputed => {
+            require!(!is_leader, DubuuMarketplaceError::WithdrawalNotAvailable);
+            false
+        }
+        // Settlement already paid the winner back whatever they escrowed above the clearing price,
+        // and a refunding dispute ruling returned the rest.
+        AuctionProcessStatus::Completed |
+        AuctionProcessStatus::Refunded if is_leader => {
+            bid_record.escrowed_usd_star = 0;
+            true
+        }
+        _ => true,
+    };
+
+    let refund = bid_record.escrowed_usd_star;
+    let auction_key_as_bytes = auction_account.key().to_bytes();
+    let escrow_authority_seeds_slices: &[&[u8]] = &[
+        b"escrow_authority".as_ref(),
+        auction_key_as_bytes.as_ref(),
+        &[auction_account.escrow_authority_bump],
+    ];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    if let Some(auction_escrow_token_account) = ctx.accounts.auction_escrow_token_account.as_mut() {
+        if refund > 0 {
+            let cpi_accounts_refund = Transfer {
+                from: auction_escrow_token_account.to_account_info(),
+                to: ctx.accounts.bidder_pern_token_account.to_account_info(),
+                authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+            };
+            let cpi_ctx_refund = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts_refund,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx_refund, refund)?;
+            bid_record.escrowed_usd_star = 0;
+        }
+
+        // The last withdrawal after the auction is over closes the escrow.
+        auction_escrow_token_account.reload()?;
+        if is_auction_over && auction_escrow_token_account.amount == 0 {
+            let cpi_accounts_close_escrow = CloseAccount {
+                account: auction_escrow_token_account.to_account_info(),
+                destination: ctx.accounts.seller_rent_recipient.to_account_info(),
+                authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+            };
+            let cpi_ctx_close_escrow = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts_close_escrow,
+                signer_seeds,
+            );
+            token::close_account(cpi_ctx_close_escrow)?;
+        }
+    } else {
+        require!(refund == 0, DubuuMarketplaceError::MissingEscrowAccount);
+    }
+
+    emit!(OutbidFundsWithdrawnEvent {
+        auction_key: auction_account.key(),
+        bidder: bid_record.bidder,
+        amount: refund,
+    });
+
+    // Mid-auction the record (and its bid history) stays, so the bidder can bid again.
+    if is_auction_over {
+        ctx.accounts.bid_record.close(ctx.accounts.bidder.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+pub fn handle_finalize_auction( ctx: Context<FinalizeAuctionAccounts>) -> Result<()> {
+    finalize_auction_outcome(
+        &mut ctx.accounts.auction_account,
+        &mut ctx.accounts.asset_account,
+        &ctx.accounts.auction_escrow_token_account,
+        &ctx.accounts.auction_escrow_authority,
+        &ctx.accounts.seller_rent_recipient,
+        &ctx.accounts.token_program.to_account_info(),
+    )
+}
+
+pub fn handle_cancel_auction(ctx: Context<CancelAuctionAccounts>) -> Result<()> {
+    let auction_account = &ctx.accounts.auction_account;
+    let marketplace_config = &ctx.accounts.marketplace_config;
+
+    let auction_key_as_bytes = auction_account.key().to_bytes();
+    let escrow_authority_seeds_slices: &[&[u8]] = &[
+        b"escrow_authority".as_ref(),
+        auction_key_as_bytes.as_ref(),
+        &[auction_account.escrow_authority_bump],
+    ];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    // The fee still sits in the program-owned vault, so the seller can cancel without the treasury co-signing.
+    let listing_fee = ctx.accounts.listing_fee_vault.amount;
+    let listing_fee_refunded = if marketplace_config.refund_listing_fee_on_cancel { listing_fee } else { 0 };
+    if listing_fee > 0 {
+        let fee_destination = if marketplace_config.refund_listing_fee_on_cancel {
+            ctx.accounts.seller_pern_token_account.to_account_info()
+        } else {
+            ctx.accounts.treasury_pern_token_account.to_account_info()
+        };
+        let cpi_accounts_release_fee = Transfer {
+            from: ctx.accounts.listing_fee_vault.to_account_info(),
+            to: fee_destination,
+            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_release_fee = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_release_fee,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_release_fee, listing_fee)?;
+    }
+
+    let cpi_accounts_close_vault = CloseAccount {
+        account: ctx.accounts.listing_fee_vault.to_account_info(),
+        destination: ctx.accounts.seller.to_account_info(),
+        authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+    };
+    let cpi_ctx_close_vault = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts_close_vault,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx_close_vault)?;
+
+    // No bids were placed, so the escrow is empty and can be closed straight away.
+    let cpi_accounts_close_escrow = CloseAccount {
+        account: ctx.accounts.auction_escrow_token_account.to_account_info(),
+        destination: ctx.accounts.seller.to_account_info(),
+        authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+    };
+    let cpi_ctx_close_escrow = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts_close_escrow,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx_close_escrow)?;
+
+    asset_module::internal_return_asset_to_ready_for_auction(
+        &mut ctx.accounts.asset_account,
+        auction_account.key()
+    )?;
+
+    emit!(AuctionCancelledBySellerEvent {
+        auction_key: auction_account.key(),
+        seller: ctx.accounts.seller.key(),
+        listing_fee_refunded,
+    });
+
+    Ok(())
+}
+
+pub fn handle_collect_listing_fee(ctx: Context<CollectListingFeeAccounts>) -> Result<()> {
+    let auction_account = &ctx.accounts.auction_account;
+    let auction_key_as_bytes = auction_account.key().to_bytes();
+    let escrow_authority_seeds_slices: &[&[u8]] = &[
+        b"escrow_authority".as_ref(),
+        auction_key_as_bytes.as_ref(),
+        &[auction_account.escrow_authority_bump],
+    ];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    let listing_fee = ctx.accounts.listing_fee_vault.amount;
+    if listing_fee > 0 {
+        let cpi_accounts_fee_to_treasury = Transfer {
+            from: ctx.accounts.listing_fee_vault.to_account_info(),
+            to: ctx.accounts.treasury_pern_token_account.to_account_info(),
+            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_fee_to_treasury = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_fee_to_treasury,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_fee_to_treasury, listing_fee)?;
+    }
+
+    let cpi_accounts_close_vault = CloseAccount {
+        account: ctx.accounts.listing_fee_vault.to_account_info(),
+        destination: ctx.accounts.seller_rent_recipient.to_account_info(),
+        authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+    };
+    let cpi_ctx_close_vault = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts_close_vault,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx_close_vault)?;
+
+    emit!(ListingFeeCollectedEvent {
+        auction_key: auction_account.key(),
+        amount: listing_fee,
+    });
+
+    Ok(())
+}
+
+pub fn handle_admin_cancel_auction(
+    ctx: Context<AdminCancelAuctionAccounts>,
+    flag_for_review: bool,
+) -> Result<()> {
+    let auction_account = &mut ctx.accounts.auction_account;
+
+    let auction_key_as_bytes = auction_account.key().to_bytes();
+    let escrow_authority_seeds_slices: &[&[u8]] = &[
+        b"escrow_authority".as_ref(),
+        auction_key_as_bytes.as_ref(),
+        &[auction_account.escrow_authority_bump],
+    ];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    // The marketplace decides up front whether an emergency unwind refunds or forfeits the listing fee.
+    let listing_fee = ctx.accounts.listing_fee_vault.amount;
+    let refund_listing_fee = ctx.accounts.marketplace_config.refund_listing_fee_on_admin_cancel;
+    let listing_fee_refunded = if refund_listing_fee { listing_fee } else { 0 };
+    if listing_fee > 0 {
+        let fee_destination = if refund_listing_fee {
+            ctx.accounts.seller_pern_token_account.to_account_info()
+        } else {
+            ctx.accounts.treasury_pern_token_account.to_account_info()
+        };
+        let cpi_accounts_release_fee = Transfer {
+            from: ctx.accounts.listing_fee_vault.to_account_info(),
+            to: fee_destination,
+            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_release_fee = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_release_fee,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_release_fee, listing_fee)?;
+    }
+
+    let cpi_accounts_close_vault = CloseAccount {
+        account: ctx.accounts.listing_fee_vault.to_account_info(),
+        destination: ctx.accounts.seller_rent_recipient.to_account_info(),
+        authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+    };
+    let cpi_ctx_close_vault = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts_close_vault,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx_close_vault)?;
+
+    // Bidders withdraw their escrowed funds themselves, so the escrow may still hold funds.
+    if ctx.accounts.auction_escrow_token_account.amount == 0 {
+        let cpi_accounts_close_escrow = CloseAccount {
+            account: ctx.accounts.auction_escrow_token_account.to_account_info(),
+            destination: ctx.accounts.seller_rent_recipient.to_account_info(),
+            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_close_escrow = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            cpi_accounts_return,
+            cpi_accounts_close_escrow,
             signer_seeds,
         );
-        token::transfer(cpi_ctx_return, auction_account.highest_bid_usd_star)?;
+        token::close_account(cpi_ctx_close_escrow)?;
+    }
+
+    auction_account.auction_status = AuctionProcessStatus::Cancelled;
+
+    let asset_account = &mut ctx.accounts.asset_account;
+    asset_module::internal_return_asset_to_ready_for_auction(asset_account, auction_account.key())?;
+    if flag_for_review {
+        asset_module::internal_flag_asset_for_review(asset_account)?;
+    }
+
+    emit!(AuctionCancelledByAdminEvent {
+        auction_key: auction_account.key(),
+        authority: ctx.accounts.authority.key(),
+        highest_bidder: auction_account.highest_bidder,
+        flagged_for_review: flag_for_review,
+        listing_fee_refunded,
+    });
+
+    Ok(())
+}
+
+pub fn handle_settle_auction_and_transfer( ctx: Context<SettleAuctionAccounts>) -> Result<()> {
+    // Funds are already escrowed, so a winner who goes quiet cannot hold the asset hostage.
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.settler.key() == ctx.accounts.highest_bidder.key() ||
+        clock.unix_timestamp >= ctx.accounts.auction_account.settlement_deadline_timestamp,
+        DubuuMarketplaceError::SettlementDeadlineNotReached
+    );
+
+    settle_auction_funds(
+        &mut ctx.accounts.auction_account,
+        &mut ctx.accounts.asset_account,
+        &mut ctx.accounts.auction_escrow_token_account,
+        &ctx.accounts.auction_escrow_authority,
+        &ctx.accounts.seller_token_account,
+        &ctx.accounts.treasury_pern_token_account,
+        &ctx.accounts.highest_bidder,
+        ctx.accounts.highest_bidder_token_account.as_ref(),
+        None,
+        &ctx.accounts.token_program.to_account_info(),
+    )
+}
+
+pub fn handle_confirm_delivery(ctx: Context<ConfirmDeliveryAccounts>) -> Result<()> {
+    release_sale_proceeds(
+        &mut ctx.accounts.auction_account,
+        &mut ctx.accounts.asset_account,
+        &mut ctx.accounts.auction_escrow_token_account,
+        &ctx.accounts.auction_escrow_authority,
+        &ctx.accounts.seller_token_account,
+        &ctx.accounts.treasury_pern_token_account,
+        &ctx.accounts.buyer.to_account_info(),
+        None,
+        &ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    emit!(DeliveryConfirmedEvent {
+        auction_key: ctx.accounts.auction_account.key(),
+        buyer: ctx.accounts.buyer.key(),
+        auto_released: false,
+    });
+
+    Ok(())
+}
+
+pub fn handle_crank_finalize_and_settle(ctx: Context<CrankFinalizeAndSettleAccounts>) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    if ctx.accounts.auction_account.auction_status == AuctionProcessStatus::AwaitingDelivery {
+        // The buyer never confirmed delivery: release the proceeds once the window lapses.
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= ctx.accounts.auction_account.delivery_deadline_timestamp,
+            DubuuMarketplaceError::DeliveryWindowOpen
+        );
+        let buyer = ctx.accounts.highest_bidder.as_ref()
+            .ok_or(DubuuMarketplaceError::NotAuctionWinner)?;
+        require!(
+            ctx.accounts.auction_account.highest_bidder == Some(buyer.key()),
+            DubuuMarketplaceError::NotAuctionWinner
+        );
+
+        release_sale_proceeds(
+            &mut ctx.accounts.auction_account,
+            &mut ctx.accounts.asset_account,
+            &mut ctx.accounts.auction_escrow_token_account,
+            &ctx.accounts.auction_escrow_authority,
+            &ctx.accounts.seller_token_account,
+            &ctx.accounts.treasury_pern_token_account,
+            buyer,
+            Some(&ctx.accounts.keeper_token_account),
+            &token_program,
+        )?;
+
+        emit!(DeliveryConfirmedEvent {
+            auction_key: ctx.accounts.auction_account.key(),
+            buyer: buyer.key(),
+            auto_released: true,
+        });
+        return Ok(());
     }
-    
-    let cpi_accounts_new_bid = Transfer {
-        from: ctx.accounts.bidder_pern_token_account.to_account_info(),
-        to: ctx.accounts.auction_escrow_token_account.to_account_info(),
-        authority: ctx.accounts.bidder.to_account_info(),
-    };
-    let cpi_ctx_new_bid = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts_new_bid,
+
+    let already_finalized = ctx.accounts.auction_account.auction_status == AuctionProcessStatus::EndedSoldPayPending;
+    if !already_finalized {
+        finalize_auction_outcome(
+            &mut ctx.accounts.auction_account,
+            &mut ctx.accounts.asset_account,
+            &ctx.accounts.auction_escrow_token_account,
+            &ctx.accounts.auction_escrow_authority,
+            &ctx.accounts.seller_rent_recipient,
+            &token_program,
+        )?;
+    }
+
+    // No sale: finalize already unwound the auction.
+    if ctx.accounts.auction_account.auction_status != AuctionProcessStatus::EndedSoldPayPending {
+        return Ok(());
+    }
+
+    // Until settlement_deadline_timestamp only the winner may settle, so the crank stops at finalizing.
+    if Clock::get()?.unix_timestamp < ctx.accounts.auction_account.settlement_deadline_timestamp {
+        require!(!already_finalized, DubuuMarketplaceError::SettlementDeadlineNotReached);
+        return Ok(());
+    }
+
+    let highest_bidder = ctx.accounts.highest_bidder.as_ref()
+        .ok_or(DubuuMarketplaceError::NotAuctionWinner)?;
+    require!(
+        ctx.accounts.auction_account.highest_bidder == Some(highest_bidder.key()),
+        DubuuMarketplaceError::NotAuctionWinner
     );
-    token::transfer(cpi_ctx_new_bid, bid_amount_usd_star)?;
+
+    settle_auction_funds(
+        &mut ctx.accounts.auction_account,
+        &mut ctx.accounts.asset_account,
+        &mut ctx.accounts.auction_escrow_token_account,
+        &ctx.accounts.auction_escrow_authority,
+        &ctx.accounts.seller_token_account,
+        &ctx.accounts.treasury_pern_token_account,
+        highest_bidder,
+        ctx.accounts.highest_bidder_token_account.as_ref(),
+        Some(&ctx.accounts.keeper_token_account),
+        &token_program,
+    )
+}
+
+pub fn handle_process_wormhole_balance_attestation(
+    ctx: Context<ProcessWormholeAttestationAccounts>,
+    payload: BalanceAttestationPayload, // This is the function argument from lib.rs
+) -> Result<()> {
+    let cross_chain_attestation = &mut ctx.accounts.cross_chain_attestation;
+    let bump = ctx.bumps.cross_chain_attestation; 
     
-    auction_account.highest_bidder = Some(ctx.accounts.bidder.key());
-    auction_account.highest_bid_usd_star = bid_amount_usd_star;
+    // The `payload_argument` from #[instruction] is used for seeds.
+    // The `payload` function argument (passed from lib.rs) is used here for the logic.
+    let hashed_asset_address = anchor_lang::solana_program::hash::hash(&payload.asset_address_on_evm_as_bytes).to_bytes();
+
+    cross_chain_attestation.user_solana_key = payload.solana_target_address;
+    cross_chain_attestation.source_chain_id = payload.evm_chain_id;
+    cross_chain_attestation.source_asset_hash = hashed_asset_address;
+    cross_chain_attestation.attested_balance = payload.balance;
+    cross_chain_attestation.attestation_timestamp = payload.timestamp;
+    cross_chain_attestation.bump = bump;
     
-    emit!(BidPlacedEvent {
-        auction_key: auction_account.key(),
-        bidder: ctx.accounts.bidder.key(),
-        amount: bid_amount_usd_star,
+    emit!(CrossChainBalanceAttestedEvent {
+        user_solana_key: payload.solana_target_address,
+        source_chain_id: payload.evm_chain_id,
+        source_asset_hash: hashed_asset_address,
+        attested_balance: payload.balance,
     });
     
     Ok(())
 }
 
-pub fn handle_finalize_auction( ctx: Context<FinalizeAuctionAccounts>) -> Result<()> {
+// --- Internal Helpers ---
+
+/// Increment required on top of `amount`: the larger of the fixed and the
+/// basis-point increment configured for the auction.
+fn bid_increment(auction_account: &AuctionAccount, amount: u64) -> Result<u64> {
+    let bps_increment = (amount as u128)
+        .checked_mul(auction_account.min_bid_increment_bps as u128)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?
+        / 10000;
+    let bps_increment = u64::try_from(bps_increment)
+        .map_err(|_| DubuuMarketplaceError::CalculationOverflow)?;
+    Ok(bps_increment.max(auction_account.min_bid_increment_usd_star))
+}
+
+/// Smallest bid that outbids the current highest bidder.
+fn minimum_outbid_amount(auction_account: &AuctionAccount) -> Result<u64> {
+    auction_account.highest_bid_usd_star
+        .checked_add(bid_increment(auction_account, auction_account.highest_bid_usd_star)?)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow.into())
+}
+
+/// Price a proxy bid settles at: one increment over the runner-up's maximum,
+/// capped at the leader's maximum.
+fn proxy_price(auction_account: &AuctionAccount, runner_up_max: u64, leader_max: u64) -> Result<u64> {
+    let increment = bid_increment(auction_account, runner_up_max)?.max(1);
+    Ok(runner_up_max.saturating_add(increment).min(leader_max))
+}
+
+/// Transfers the marketplace listing fee from the seller to `fee_destination_token_account`:
+/// the treasury for fixed-price listings, the auction's fee vault for auctions.
+pub fn internal_collect_listing_fee<'info>(
+    seller_token_account: AccountInfo<'info>,
+    fee_destination_token_account: AccountInfo<'info>,
+    seller: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    listing_fee_usd_star: u64,
+) -> Result<()> {
+    let cpi_accounts_fee = Transfer {
+        from: seller_token_account,
+        to: fee_destination_token_account,
+        authority: seller,
+    };
+    let cpi_ctx_fee = CpiContext::new(token_program, cpi_accounts_fee);
+    token::transfer(cpi_ctx_fee, listing_fee_usd_star)
+}
+
+/// Marketplace commission owed on a sale of `amount`.
+pub fn internal_sale_commission(amount: u64, commission_bps: u16) -> Result<u64> {
+    amount
+        .checked_mul(commission_bps as u64)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?
+        .checked_div(10000)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow.into())
+}
+
+/// Share of the marketplace commission paid to the keeper that cranks settlement.
+fn keeper_fee(commission: u64, keeper_fee_bps: u16) -> Result<u64> {
+    (commission as u128)
+        .checked_mul(keeper_fee_bps as u128)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?
+        .checked_div(10000)
+        .and_then(|fee| u64::try_from(fee).ok())
+        .ok_or(DubuuMarketplaceError::CalculationOverflow.into())
+}
+
+/// Appends a bid to the bidder's on-chain history, dropping the oldest entry once full.
+pub fn internal_record_bid(bid_record: &mut BidRecord, amount: u64, timestamp: i64) {
+    if bid_record.bid_history.len() >= MAX_BID_HISTORY_ENTRIES {
+        bid_record.bid_history.remove(0);
+    }
+    bid_record.bid_history.push(BidHistoryEntry { amount, timestamp });
+}
+
+// Shared with the sealed-bid module, whose first commit may open a scheduled auction.
+pub fn internal_activate_pending_auction(auction_account: &mut Account<AuctionAccount>) -> Result<()> {
+    auction_account.auction_status = AuctionProcessStatus::Active;
+
+    emit!(AuctionStartedEvent {
+        auction_key: auction_account.key(),
+        auction_start_timestamp: auction_account.auction_start_timestamp,
+        auction_end_timestamp: auction_account.auction_end_timestamp,
+    });
+
+    Ok(())
+}
+
+// Shared listing path for every auction type: charges the listing fee, validates the
+// schedule and links the asset. Type-specific fields are filled in by the caller.
+fn open_auction(
+    ctx: &mut Context<ListAssetForAuctionAccounts>,
+    auction_type: AuctionType,
+    start_price_usd_star: u64,
+    duration_seconds: i64,
+    start_timestamp: Option<i64>,
+) -> Result<()> {
     let auction_account = &mut ctx.accounts.auction_account;
+    let marketplace_config = &ctx.accounts.marketplace_config;
+    let asset_account = &mut ctx.accounts.asset_account;
+
+    require!(
+        duration_seconds >= marketplace_config.min_auction_duration_seconds,
+        DubuuMarketplaceError::AuctionDurationTooShort
+    );
+    require!(
+        duration_seconds <= marketplace_config.max_auction_duration_seconds,
+        DubuuMarketplaceError::AuctionDurationTooLong
+    );
+
+    let auction_account_bump = ctx.bumps.auction_account;
+    let auction_escrow_authority_bump = ctx.bumps.auction_escrow_authority;
+    
+    internal_collect_listing_fee(
+        ctx.accounts.seller_pern_token_account.to_account_info(),
+        ctx.accounts.listing_fee_vault.to_account_info(),
+        ctx.accounts.seller.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        marketplace_config.listing_fee_usd_star,
+    )?;
+    
+    let clock = Clock::get()?;
+    // Without an explicit start time the auction opens immediately.
+    let auction_start_timestamp = start_timestamp.unwrap_or(clock.unix_timestamp);
+    require!(
+        auction_start_timestamp >= clock.unix_timestamp,
+        DubuuMarketplaceError::InvalidAuctionStartTime
+    );
+    require!(
+        auction_start_timestamp - clock.unix_timestamp <= marketplace_config.max_auction_start_delay_seconds,
+        DubuuMarketplaceError::AuctionStartTooFarInFuture
+    );
+    let auction_end_timestamp = auction_start_timestamp.checked_add(duration_seconds)
+        .ok_or(DubuuMarketplaceError::TimestampOverflow)?;
+    
+    auction_account.asset_key = asset_account.key();
+    auction_account.seller = ctx.accounts.seller.key();
+    auction_account.pern_usd_star_mint = marketplace_config.pern_usd_star_mint;
+    auction_account.start_price_usd_star = start_price_usd_star;
+    auction_account.auction_end_timestamp = auction_end_timestamp;
+    auction_account.highest_bid_usd_star = start_price_usd_star; 
+    auction_account.highest_bidder = None;
+    auction_account.auction_status = if auction_start_timestamp > clock.unix_timestamp {
+        AuctionProcessStatus::PendingStart
+    } else {
+        AuctionProcessStatus::Active
+    };
+    auction_account.escrow_authority_bump = auction_escrow_authority_bump;
+    auction_account.bump = auction_account_bump;
+    auction_account.listing_nonce = asset_account.auction_count;
+    auction_account.listing_fee_paid_usd_star = marketplace_config.listing_fee_usd_star;
+    auction_account.auction_start_timestamp = auction_start_timestamp;
+    auction_account.auction_type = auction_type;
+    auction_account.settlement_grace_period_seconds = marketplace_config.settlement_grace_period_seconds;
+    auction_account.delivery_confirmation_period_seconds = marketplace_config.delivery_confirmation_period_seconds;
+    auction_account.sale_commission_bps = marketplace_config.sale_commission_bps;
+    auction_account.keeper_fee_bps = marketplace_config.keeper_fee_bps;
     
+    asset_module::internal_update_asset_status_to_in_auction(
+        asset_account,
+        auction_account.key()
+    )
+}
+
+/// Ends a finished auction: picks the winner and opens the settlement window, or
+/// records the no-sale outcome and hands the asset back to the seller.
+fn finalize_auction_outcome<'info>(
+    auction_account: &mut Account<'info, AuctionAccount>,
+    asset_account: &mut Account<'info, AssetAccount>,
+    auction_escrow_token_account: &Account<'info, TokenAccount>,
+    auction_escrow_authority: &AccountInfo<'info>,
+    seller_rent_recipient: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
     require!(
-        auction_account.auction_status == AuctionProcessStatus::Active,
+        auction_account.auction_status == AuctionProcessStatus::Active ||
+        auction_account.auction_status == AuctionProcessStatus::PendingStart,
         DubuuMarketplaceError::AuctionNotInActiveState
     );
 
+    // Sealed-bid auctions only end once the reveal phase is over.
+    let is_sealed_bid = auction_account.auction_type == AuctionType::SealedBid;
+    let bidding_end_timestamp = if is_sealed_bid {
+        auction_account.reveal_end_timestamp
+    } else {
+        auction_account.auction_end_timestamp
+    };
+
     let clock = Clock::get()?;
     require!(
-        clock.unix_timestamp >= auction_account.auction_end_timestamp,
+        clock.unix_timestamp >= bidding_end_timestamp,
         DubuuMarketplaceError::AuctionNotEnded
     );
     
@@ -418,54 +1731,174 @@ pub fn handle_finalize_auction( ctx: Context<FinalizeAuctionAccounts>) -> Result
         auction_key_as_bytes.as_ref(),
         &[auction_account.escrow_authority_bump],
     ];
-    let signer_seeds = &[&escrow_authority_seeds_slices[..]];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    let reserve_not_met = auction_account.reserve_price_usd_star
+        .is_some_and(|reserve_price| auction_account.highest_bid_usd_star < reserve_price);
 
-    if auction_account.highest_bidder.is_some() {
-        auction_account.auction_status = AuctionProcessStatus::EndedSoldPayPending;
+    if auction_account.highest_bidder.is_some() && !reserve_not_met {
+        // Second-price auctions charge the runner-up bid, floored at the reserve and minimum bid.
+        auction_account.clearing_price_usd_star = if auction_account.is_second_price {
+            auction_account.second_highest_bid_usd_star
+                .max(auction_account.reserve_price_usd_star.unwrap_or_default())
+                .max(auction_account.start_price_usd_star)
+        } else {
+            auction_account.highest_bid_usd_star
+        };
+        open_settlement_window(auction_account, clock.unix_timestamp)?;
         emit!(AuctionEndedWinner {
             auction_key: auction_account.key(),
             winner: auction_account.highest_bidder.unwrap(),
             winning_bid: auction_account.highest_bid_usd_star,
+            clearing_price: auction_account.clearing_price_usd_star,
         });
-    } else { 
-        auction_account.auction_status = AuctionProcessStatus::EndedUnsold;
-        emit!(AuctionEndedNoSale {
-            auction_key: auction_account.key(),
-        });
+    } else {
+        if let Some(highest_bidder_key) = auction_account.highest_bidder {
+            // Bidders reclaim their escrowed funds through withdraw_outbid_funds or withdraw_sealed_bid.
+            auction_account.auction_status = AuctionProcessStatus::EndedReserveNotMet;
+            emit!(AuctionEndedReserveNotMet {
+                auction_key: auction_account.key(),
+                highest_bidder: highest_bidder_key,
+                highest_bid: auction_account.highest_bid_usd_star,
+                reserve_price: auction_account.reserve_price_usd_star.unwrap_or_default(),
+            });
+        } else {
+            auction_account.auction_status = AuctionProcessStatus::EndedUnsold;
+            emit!(AuctionEndedNoSale {
+                auction_key: auction_account.key(),
+            });
+        }
 
-        let cpi_accounts_close_escrow = CloseAccount {
-            account: ctx.accounts.auction_escrow_token_account.to_account_info(),
-            destination: ctx.accounts.seller_rent_recipient.to_account_info(),
-            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
-        };
-        let cpi_ctx_close_escrow = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts_close_escrow,
-            signer_seeds,
-        );
-        token::close_account(cpi_ctx_close_escrow)?;
+        // Unclaimed bids keep the escrow open; the last withdrawal closes it.
+        if auction_escrow_token_account.amount == 0 {
+            let cpi_accounts_close_escrow = CloseAccount {
+                account: auction_escrow_token_account.to_account_info(),
+                destination: seller_rent_recipient.to_account_info(),
+                authority: auction_escrow_authority.to_account_info(),
+            };
+            let cpi_ctx_close_escrow = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                cpi_accounts_close_escrow,
+                signer_seeds,
+            );
+            token::close_account(cpi_ctx_close_escrow)?;
+        }
+
+        asset_module::internal_return_asset_to_ready_for_auction(
+            asset_account,
+            auction_account.key()
+        )?;
     }
     
     Ok(())
 }
 
-pub fn handle_settle_auction_and_transfer( ctx: Context<SettleAuctionAccounts>) -> Result<()> {
-    let auction_account = &mut ctx.accounts.auction_account;
-    let marketplace_config = &ctx.accounts.marketplace_config;
-    let asset_account = &mut ctx.accounts.asset_account;
-    
-    let commission_bps = marketplace_config.sale_commission_bps as u64;
-    let total_bid_amount = auction_account.highest_bid_usd_star;
+/// Settles a won auction: returns the winner's excess over the clearing price, then
+/// either releases the proceeds right away or, when the auction requires delivery
+/// confirmation, holds them in escrow until `confirm_delivery` or the delivery timeout.
+#[allow(clippy::too_many_arguments)]
+fn settle_auction_funds<'info>(
+    auction_account: &mut Account<'info, AuctionAccount>,
+    asset_account: &mut Account<'info, AssetAccount>,
+    auction_escrow_token_account: &mut Account<'info, TokenAccount>,
+    auction_escrow_authority: &AccountInfo<'info>,
+    seller_token_account: &Account<'info, TokenAccount>,
+    treasury_pern_token_account: &Account<'info, TokenAccount>,
+    highest_bidder: &AccountInfo<'info>,
+    highest_bidder_token_account: Option<&Account<'info, TokenAccount>>,
+    keeper: Option<&Account<'info, TokenAccount>>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    // Whatever the winner escrowed above the clearing price (proxy maximum or second-price bid) goes back to them.
+    let winner_refund = auction_account.highest_max_bid_usd_star
+        .checked_sub(auction_account.clearing_price_usd_star)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
 
-    let commission = total_bid_amount
-        .checked_mul(commission_bps)
-        .ok_or_else(|| DubuuMarketplaceError::CalculationOverflow)?
-        .checked_div(10000)
-        .ok_or_else(|| DubuuMarketplaceError::CalculationOverflow)?;
+    let auction_key_as_bytes = auction_account.key().to_bytes();
+    let escrow_authority_seeds_slices: &[&[u8]] = &[
+        b"escrow_authority".as_ref(),
+        auction_key_as_bytes.as_ref(),
+        &[auction_account.escrow_authority_bump],
+    ];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    if winner_refund > 0 {
+        let winner_token_account = highest_bidder_token_account
+            .ok_or(DubuuMarketplaceError::MissingHighestBidderRefundAccount)?;
+        require_keys_eq!(winner_token_account.owner, highest_bidder.key(), DubuuMarketplaceError::InvalidTokenAccountOwner);
+        require_keys_eq!(winner_token_account.mint, auction_account.pern_usd_star_mint, DubuuMarketplaceError::InvalidPerenaMint);
+
+        let cpi_accounts_to_winner = Transfer {
+            from: auction_escrow_token_account.to_account_info(),
+            to: winner_token_account.to_account_info(),
+            authority: auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_to_winner = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts_to_winner,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_to_winner, winner_refund)?;
+    }
+
+    if auction_account.delivery_confirmation_period_seconds > 0 {
+        let clock = Clock::get()?;
+        auction_account.delivery_deadline_timestamp = clock.unix_timestamp
+            .checked_add(auction_account.delivery_confirmation_period_seconds)
+            .ok_or(DubuuMarketplaceError::TimestampOverflow)?;
+        auction_account.auction_status = AuctionProcessStatus::AwaitingDelivery;
+
+        emit!(AwaitingDeliveryEvent {
+            auction_key: auction_account.key(),
+            buyer: highest_bidder.key(),
+            delivery_deadline_timestamp: auction_account.delivery_deadline_timestamp,
+        });
+        return Ok(());
+    }
+
+    release_sale_proceeds(
+        auction_account,
+        asset_account,
+        auction_escrow_token_account,
+        auction_escrow_authority,
+        seller_token_account,
+        treasury_pern_token_account,
+        highest_bidder,
+        keeper,
+        token_program,
+    )
+}
+
+/// Pays the clearing price out of escrow (seller, treasury and optionally a keeper),
+/// transfers the asset to the buyer and completes the auction.
+#[allow(clippy::too_many_arguments)]
+fn release_sale_proceeds<'info>(
+    auction_account: &mut Account<'info, AuctionAccount>,
+    asset_account: &mut Account<'info, AssetAccount>,
+    auction_escrow_token_account: &mut Account<'info, TokenAccount>,
+    auction_escrow_authority: &AccountInfo<'info>,
+    seller_token_account: &Account<'info, TokenAccount>,
+    treasury_pern_token_account: &Account<'info, TokenAccount>,
+    buyer: &AccountInfo<'info>,
+    keeper: Option<&Account<'info, TokenAccount>>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let total_bid_amount = auction_account.clearing_price_usd_star;
+    // Terms were snapshotted at listing, so config changes mid-auction don't alter the payout.
+    let commission = internal_sale_commission(total_bid_amount, auction_account.sale_commission_bps)?;
         
     let amount_to_seller = total_bid_amount
         .checked_sub(commission)
-        .ok_or_else(|| DubuuMarketplaceError::CalculationOverflow)?;
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    // The crank's keeper is paid out of the marketplace's commission, never the seller's share.
+    let keeper_fee = match keeper {
+        Some(_) => keeper_fee(commission, auction_account.keeper_fee_bps)?,
+        None => 0,
+    };
+    let commission = commission
+        .checked_sub(keeper_fee)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
     
     let auction_key_as_bytes = auction_account.key().to_bytes();
     let escrow_authority_seeds_slices: &[&[u8]] = &[
@@ -473,16 +1906,16 @@ pub fn handle_settle_auction_and_transfer( ctx: Context<SettleAuctionAccounts>)
         auction_key_as_bytes.as_ref(),
         &[auction_account.escrow_authority_bump],
     ];
-    let signer_seeds = &[&escrow_authority_seeds_slices[..]];
+    let signer_seeds = &[escrow_authority_seeds_slices];
     
     if amount_to_seller > 0 {
         let cpi_accounts_to_seller = Transfer {
-            from: ctx.accounts.auction_escrow_token_account.to_account_info(),
-            to: ctx.accounts.seller_token_account.to_account_info(),
-            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+            from: auction_escrow_token_account.to_account_info(),
+            to: seller_token_account.to_account_info(),
+            authority: auction_escrow_authority.to_account_info(),
         };
         let cpi_ctx_to_seller = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            token_program.to_account_info(),
             cpi_accounts_to_seller,
             signer_seeds,
         );
@@ -491,33 +1924,57 @@ pub fn handle_settle_auction_and_transfer( ctx: Context<SettleAuctionAccounts>)
     
     if commission > 0 {
         let cpi_accounts_to_treasury = Transfer {
-            from: ctx.accounts.auction_escrow_token_account.to_account_info(),
-            to: ctx.accounts.treasury_pern_token_account.to_account_info(),
-            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+            from: auction_escrow_token_account.to_account_info(),
+            to: treasury_pern_token_account.to_account_info(),
+            authority: auction_escrow_authority.to_account_info(),
         };
         let cpi_ctx_to_treasury = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            token_program.to_account_info(),
             cpi_accounts_to_treasury,
             signer_seeds,
         );
         token::transfer(cpi_ctx_to_treasury, commission)?;
     }
-    
-    let cpi_accounts_close_escrow = CloseAccount {
-        account: ctx.accounts.auction_escrow_token_account.to_account_info(),
-        destination: ctx.accounts.highest_bidder.to_account_info(),
-        authority: ctx.accounts.auction_escrow_authority.to_account_info(),
-    };
-    let cpi_ctx_close_escrow = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts_close_escrow,
-        signer_seeds,
-    );
-    token::close_account(cpi_ctx_close_escrow)?;
+
+    if let Some(keeper_token_account) = keeper.filter(|_| keeper_fee > 0) {
+        let cpi_accounts_to_keeper = Transfer {
+            from: auction_escrow_token_account.to_account_info(),
+            to: keeper_token_account.to_account_info(),
+            authority: auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_to_keeper = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts_to_keeper,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_to_keeper, keeper_fee)?;
+
+        emit!(KeeperFeePaidEvent {
+            auction_key: auction_account.key(),
+            keeper: keeper_token_account.owner,
+            amount: keeper_fee,
+        });
+    }
+
+    // Losing sealed-bid deposits may still sit in escrow until their owners withdraw them.
+    auction_escrow_token_account.reload()?;
+    if auction_escrow_token_account.amount == 0 {
+        let cpi_accounts_close_escrow = CloseAccount {
+            account: auction_escrow_token_account.to_account_info(),
+            destination: buyer.to_account_info(),
+            authority: auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_close_escrow = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts_close_escrow,
+            signer_seeds,
+        );
+        token::close_account(cpi_ctx_close_escrow)?;
+    }
     
     asset_module::internal_transfer_ownership(
         asset_account,
-        ctx.accounts.highest_bidder.key()
+        buyer.key()
     )?;
     
     auction_account.auction_status = AuctionProcessStatus::Completed;
@@ -525,30 +1982,180 @@ pub fn handle_settle_auction_and_transfer( ctx: Context<SettleAuctionAccounts>)
     Ok(())
 }
 
-pub fn handle_process_wormhole_balance_attestation(
-    ctx: Context<ProcessWormholeAttestationAccounts>,
-    payload: BalanceAttestationPayload, // This is the function argument from lib.rs
-) -> Result<()> {
-    let cross_chain_attestation = &mut ctx.accounts.cross_chain_attestation;
-    let bump = ctx.bumps.cross_chain_attestation; 
-    
-    // The `payload_argument` from #[instruction] is used for seeds.
-    // The `payload` function argument (passed from lib.rs) is used here for the logic.
-    let hashed_asset_address = anchor_lang::solana_program::hash::hash(&payload.asset_address_on_evm_as_bytes).to_bytes();
-
-    cross_chain_attestation.user_solana_key = payload.solana_target_address;
-    cross_chain_attestation.source_chain_id = payload.evm_chain_id;
-    cross_chain_attestation.source_asset_hash = hashed_asset_address;
-    cross_chain_attestation.attested_balance = payload.balance;
-    cross_chain_attestation.attestation_timestamp = payload.timestamp;
-    cross_chain_attestation.bump = bump;
-    
-    emit!(CrossChainBalanceAttestedEvent {
-        user_solana_key: payload.solana_target_address,
-        source_chain_id: payload.evm_chain_id,
-        source_asset_hash: hashed_asset_address,
-        attested_balance: payload.balance,
-    });
-    
+/// Moves a won auction to `EndedSoldPayPending`; the winner has the auction's grace
+/// period to settle before anyone may settle for them.
+fn open_settlement_window(auction_account: &mut AuctionAccount, now: i64) -> Result<()> {
+    auction_account.auction_status = AuctionProcessStatus::EndedSoldPayPending;
+    auction_account.settlement_deadline_timestamp = now
+        .checked_add(auction_account.settlement_grace_period_seconds)
+        .ok_or(DubuuMarketplaceError::TimestampOverflow)?;
     Ok(())
 }
+
+/// Dutch price decays from the start price to the floor over the auction duration,
+/// either continuously (step of 0) or in whole steps of `dutch_price_step_seconds`.
+fn current_dutch_price(auction_account: &AuctionAccount, now: i64) -> Result<u64> {
+    let duration = auction_account.auction_end_timestamp
+        .checked_sub(auction_account.auction_start_timestamp)
+        .ok_or(DubuuMarketplaceError::TimestampOverflow)?;
+    let mut elapsed = now
+        .checked_sub(auction_account.auction_start_timestamp)
+        .ok_or(DubuuMarketplaceError::TimestampOverflow)?
+        .clamp(0, duration);
+    if auction_account.dutch_price_step_seconds > 0 {
+        elapsed -= elapsed % auction_account.dutch_price_step_seconds;
+    }
+
+    let price_range = auction_account.start_price_usd_star
+        .checked_sub(auction_account.dutch_floor_price_usd_star)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+    let price_drop = (price_range as u128)
+        .checked_mul(elapsed as u128)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?
+        .checked_div(duration as u128)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    Ok(auction_account.start_price_usd_star - price_drop as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english_auction() -> AuctionAccount {
+        AuctionAccount {
+            asset_key: Pubkey::default(),
+            seller: Pubkey::default(),
+            pern_usd_star_mint: Pubkey::default(),
+            start_price_usd_star: 1_000,
+            auction_end_timestamp: 1_000,
+            highest_bid_usd_star: 1_000,
+            highest_bidder: None,
+            auction_status: AuctionProcessStatus::Active,
+            escrow_authority_bump: 0,
+            bump: 0,
+            reserve_price_usd_star: None,
+            is_reserve_price_hidden: false,
+            anti_snipe_window_seconds: 0,
+            anti_snipe_extension_seconds: 0,
+            min_bid_increment_usd_star: 0,
+            min_bid_increment_bps: 0,
+            buy_now_price_usd_star: None,
+            listing_nonce: 0,
+            listing_fee_paid_usd_star: 0,
+            auction_start_timestamp: 0,
+            auction_type: AuctionType::English,
+            dutch_floor_price_usd_star: 0,
+            dutch_price_step_seconds: 0,
+            bid_count: 0,
+            reveal_end_timestamp: 0,
+            sealed_bid_deposit_usd_star: 0,
+            sealed_bid_slash_bps: 0,
+            is_second_price: false,
+            second_highest_bid_usd_star: 0,
+            clearing_price_usd_star: 0,
+            highest_max_bid_usd_star: 0,
+            settlement_grace_period_seconds: 0,
+            settlement_deadline_timestamp: 0,
+            delivery_confirmation_period_seconds: 0,
+            delivery_deadline_timestamp: 0,
+            sale_commission_bps: 0,
+            keeper_fee_bps: 0,
+        }
+    }
+
+    fn dutch_auction(step_seconds: i64) -> AuctionAccount {
+        AuctionAccount {
+            auction_type: AuctionType::Dutch,
+            dutch_floor_price_usd_star: 200,
+            dutch_price_step_seconds: step_seconds,
+            ..english_auction()
+        }
+    }
+
+    #[test]
+    fn dutch_price_decays_linearly_between_start_and_floor() {
+        let auction = dutch_auction(0);
+        assert_eq!(current_dutch_price(&auction, 0).unwrap(), 1_000);
+        assert_eq!(current_dutch_price(&auction, 250).unwrap(), 800);
+        assert_eq!(current_dutch_price(&auction, 500).unwrap(), 600);
+        assert_eq!(current_dutch_price(&auction, 1_000).unwrap(), 200);
+    }
+
+    #[test]
+    fn dutch_price_is_clamped_outside_the_auction_window() {
+        let auction = dutch_auction(0);
+        assert_eq!(current_dutch_price(&auction, -50).unwrap(), 1_000);
+        assert_eq!(current_dutch_price(&auction, 5_000).unwrap(), 200);
+    }
+
+    #[test]
+    fn dutch_price_drops_in_whole_steps() {
+        let auction = dutch_auction(250);
+        assert_eq!(current_dutch_price(&auction, 249).unwrap(), 1_000);
+        assert_eq!(current_dutch_price(&auction, 250).unwrap(), 800);
+        assert_eq!(current_dutch_price(&auction, 499).unwrap(), 800);
+        assert_eq!(current_dutch_price(&auction, 750).unwrap(), 400);
+        assert_eq!(current_dutch_price(&auction, 1_000).unwrap(), 200);
+    }
+
+    #[test]
+    fn sale_commission_rounds_down() {
+        assert_eq!(internal_sale_commission(1_000, 250).unwrap(), 25);
+        assert_eq!(internal_sale_commission(39, 250).unwrap(), 0);
+        assert_eq!(internal_sale_commission(1_000, 0).unwrap(), 0);
+        assert_eq!(internal_sale_commission(1_000, 10_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn sale_commission_overflow_is_an_error() {
+        assert!(internal_sale_commission(u64::MAX, 250).is_err());
+    }
+
+    #[test]
+    fn keeper_fee_is_a_share_of_the_commission() {
+        assert_eq!(keeper_fee(1_000, 1_000).unwrap(), 100);
+        assert_eq!(keeper_fee(9, 1_000).unwrap(), 0);
+        assert_eq!(keeper_fee(1_000, 0).unwrap(), 0);
+        assert_eq!(keeper_fee(u64::MAX, 10_000).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn bid_increment_takes_the_larger_of_fixed_and_bps() {
+        let auction = AuctionAccount {
+            min_bid_increment_usd_star: 30,
+            min_bid_increment_bps: 500,
+            ..english_auction()
+        };
+        assert_eq!(bid_increment(&auction, 400).unwrap(), 30);
+        assert_eq!(bid_increment(&auction, 1_000).unwrap(), 50);
+    }
+
+    #[test]
+    fn minimum_outbid_adds_one_increment_to_the_visible_price() {
+        let auction = AuctionAccount {
+            highest_bid_usd_star: 1_000,
+            min_bid_increment_usd_star: 10,
+            min_bid_increment_bps: 500,
+            ..english_auction()
+        };
+        assert_eq!(minimum_outbid_amount(&auction).unwrap(), 1_050);
+    }
+
+    #[test]
+    fn proxy_price_is_one_increment_over_the_runner_up_capped_at_the_leader() {
+        let auction = AuctionAccount {
+            min_bid_increment_usd_star: 10,
+            ..english_auction()
+        };
+        assert_eq!(proxy_price(&auction, 1_200, 2_000).unwrap(), 1_210);
+        assert_eq!(proxy_price(&auction, 1_995, 2_000).unwrap(), 2_000);
+    }
+
+    #[test]
+    fn proxy_price_moves_at_least_one_unit_without_increments() {
+        let auction = english_auction();
+        assert_eq!(proxy_price(&auction, 1_200, 2_000).unwrap(), 1_201);
+        assert_eq!(proxy_price(&auction, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+    }
+}
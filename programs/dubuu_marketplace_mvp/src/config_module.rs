@@ -1,17 +1,27 @@
 use anchor_lang::prelude::*;
 // use anchor_spl::token::Token; // Removed unused import
-use anchor_spl::token::{Mint, TokenAccount}; 
+use anchor_lang::system_program;
+use anchor_spl::token::Mint;
 use crate::state::*;
 use crate::errors::*;
 
 pub const PERENA_USD_STAR_MINT_PUBKEY_STR: &str = "BenJy1n3WTx9mTjEvy63e8Q1j4RqUc6E4VBMz3ir4Wo6";
+pub const DEFAULT_MAX_AUCTION_START_DELAY_SECONDS: i64 = 30 * 24 * 60 * 60; // 30 days
+pub const DEFAULT_MIN_AUCTION_DURATION_SECONDS: i64 = 60 * 60; // 1 hour
+pub const DEFAULT_MAX_AUCTION_DURATION_SECONDS: i64 = 30 * 24 * 60 * 60; // 30 days
+pub const DEFAULT_SETTLEMENT_GRACE_PERIOD_SECONDS: i64 = 7 * 24 * 60 * 60; // 7 days
+pub const DEFAULT_CONFIG_CHANGE_DELAY_SECONDS: i64 = 2 * 24 * 60 * 60; // 2 days
+pub const MARKETPLACE_CONFIG_SPACE: usize = 8 + 32 + 32 + 32 + 8 + 2 + 1 + 1 + 8 + 8 + 8 + 2 + 1 + 8 + 8 + 8 + 2 + 8 + 2 + 8 + (1 + 32) + (4 + MAX_CONFIG_SIGNERS * 32) + 1 + 8 + 8 + 1;
+// Configs initialized before any of the fields after `bump` existed.
+pub const LEGACY_MARKETPLACE_CONFIG_SPACE: usize = 8 + 32 + 32 + 32 + 8 + 2 + 1 + 1;
+const ROLE_REGISTRY_SPACE: usize = 8 + (4 + MAX_ROLE_ASSIGNMENTS * (1 + 32)) + 1;
 
 #[derive(Accounts)]
 pub struct InitializeConfigAccounts<'info> {
     #[account(
         init,
         payer = signer,
-        space = 8 + 32 + 32 + 32 + 8 + 2 + 1 + 1, 
+        space = MARKETPLACE_CONFIG_SPACE,
         seeds = [b"marketplace_config".as_ref()],
         bump
     )]
@@ -21,6 +31,15 @@ pub struct InitializeConfigAccounts<'info> {
         constraint = pern_usd_star_mint.key() == PERENA_USD_STAR_MINT_PUBKEY_STR.parse::<Pubkey>().unwrap() @ DubuuMarketplaceError::InvalidPerenaMint
     )]
     pub pern_usd_star_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = ROLE_REGISTRY_SPACE,
+        seeds = [b"role_registry".as_ref()],
+        bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
     
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -28,16 +47,68 @@ pub struct InitializeConfigAccounts<'info> {
     pub system_program: Program<'info, System>,
 }
 
+// Brings a config deployed before the post-`bump` fields existed up to the current layout and
+// creates its RoleRegistry. `init` on the registry makes it one-time.
+#[derive(Accounts)]
+pub struct MigrateConfigAccounts<'info> {
+    /// CHECK: Still in the legacy layout, so it cannot load as Account<MarketplaceConfig> yet. The handler checks the size and the admin once it is grown.
+    #[account(
+        mut,
+        seeds = [b"marketplace_config".as_ref()],
+        bump
+    )]
+    pub marketplace_config: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ROLE_REGISTRY_SPACE,
+        seeds = [b"role_registry".as_ref()],
+        bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Each field update in handle_update_config checks the authority against the role that owns it.
 #[derive(Accounts)]
 pub struct UpdateConfigAccounts<'info> {
     #[account(
         mut,
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        seeds = [b"role_registry".as_ref()],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ManageRoleAccounts<'info> {
+    #[account(
         seeds = [b"marketplace_config".as_ref()],
         bump = marketplace_config.bump,
         has_one = admin @ DubuuMarketplaceError::Unauthorized
     )]
     pub marketplace_config: Account<'info, MarketplaceConfig>,
-    
+
+    #[account(
+        mut,
+        seeds = [b"role_registry".as_ref()],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+
     pub admin: Signer<'info>,
 }
 
@@ -56,39 +127,267 @@ pub fn handle_initialize_config(
     marketplace_config.sale_commission_bps = sale_commission_bps;
     marketplace_config.is_paused = false;
     marketplace_config.bump = ctx.bumps.marketplace_config;
+    internal_apply_default_settings(marketplace_config, admin);
+
+    let role_registry = &mut ctx.accounts.role_registry;
+    role_registry.assignments = default_role_assignments(admin);
+    role_registry.bump = ctx.bumps.role_registry;
     
     Ok(())
 }
 
+pub fn handle_migrate_config(ctx: Context<MigrateConfigAccounts>) -> Result<()> {
+    let config_info = &ctx.accounts.marketplace_config;
+    require!(
+        config_info.data_len() == LEGACY_MARKETPLACE_CONFIG_SPACE,
+        DubuuMarketplaceError::AccountNotMigratable
+    );
+
+    // The appended bytes are zeroed, which deserializes as an empty signer set and no pending admin.
+    internal_grow_account(
+        config_info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        MARKETPLACE_CONFIG_SPACE,
+    )?;
+    let mut marketplace_config = MarketplaceConfig::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+    let admin = ctx.accounts.admin.key();
+    require_keys_eq!(marketplace_config.admin, admin, DubuuMarketplaceError::Unauthorized);
+
+    internal_apply_default_settings(&mut marketplace_config, admin);
+    marketplace_config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    let role_registry = &mut ctx.accounts.role_registry;
+    role_registry.assignments = default_role_assignments(admin);
+    role_registry.bump = ctx.bumps.role_registry;
+
+    Ok(())
+}
+
+// Everything after `bump`, shared by fresh deployments and migrated ones.
+fn internal_apply_default_settings(marketplace_config: &mut MarketplaceConfig, admin: Pubkey) {
+    // Anti-sniping is disabled until the admin configures it through update_config.
+    marketplace_config.anti_snipe_window_seconds = 0;
+    marketplace_config.anti_snipe_extension_seconds = 0;
+    marketplace_config.min_bid_increment_usd_star = 0;
+    marketplace_config.min_bid_increment_bps = 0;
+    marketplace_config.refund_listing_fee_on_cancel = false;
+    marketplace_config.max_auction_start_delay_seconds = DEFAULT_MAX_AUCTION_START_DELAY_SECONDS;
+    marketplace_config.min_auction_duration_seconds = DEFAULT_MIN_AUCTION_DURATION_SECONDS;
+    marketplace_config.max_auction_duration_seconds = DEFAULT_MAX_AUCTION_DURATION_SECONDS;
+    marketplace_config.sealed_bid_slash_bps = 0;
+    marketplace_config.settlement_grace_period_seconds = DEFAULT_SETTLEMENT_GRACE_PERIOD_SECONDS;
+    marketplace_config.keeper_fee_bps = 0;
+    marketplace_config.delivery_confirmation_period_seconds = 0;
+    marketplace_config.pending_admin = None;
+    marketplace_config.config_signers = vec![admin];
+    marketplace_config.config_approval_threshold = 1;
+    marketplace_config.config_proposal_count = 0;
+    marketplace_config.config_change_delay_seconds = DEFAULT_CONFIG_CHANGE_DELAY_SECONDS;
+    marketplace_config.refund_listing_fee_on_admin_cancel = false;
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn handle_update_config(
     ctx: Context<UpdateConfigAccounts>,
-    new_listing_fee: Option<u64>,
-    new_sale_commission: Option<u16>,
-    new_treasury: Option<Pubkey>,
     new_paused_status: Option<bool>,
-    new_admin: Option<Pubkey>,
+    new_anti_snipe_window_seconds: Option<i64>,
+    new_anti_snipe_extension_seconds: Option<i64>,
+    new_min_bid_increment_usd_star: Option<u64>,
+    new_min_bid_increment_bps: Option<u16>,
+    new_refund_listing_fee_on_cancel: Option<bool>,
+    new_max_auction_start_delay_seconds: Option<i64>,
+    new_min_auction_duration_seconds: Option<i64>,
+    new_max_auction_duration_seconds: Option<i64>,
+    new_sealed_bid_slash_bps: Option<u16>,
+    new_settlement_grace_period_seconds: Option<i64>,
+    new_delivery_confirmation_period_seconds: Option<i64>,
+    new_refund_listing_fee_on_admin_cancel: Option<bool>,
 ) -> Result<()> {
     let marketplace_config = &mut ctx.accounts.marketplace_config;
+    let role_registry = &ctx.accounts.role_registry;
+    let authority = ctx.accounts.authority.key();
     
-    if let Some(fee) = new_listing_fee {
-        marketplace_config.listing_fee_usd_star = fee;
+    // Fees, commission, treasury_pern_account and admin only change through timelocked governance proposals.
+    if let Some(paused) = new_paused_status {
+        internal_require_role(role_registry, MarketplaceRole::Pauser, authority)?;
+        marketplace_config.is_paused = paused;
     }
-    
-    if let Some(commission) = new_sale_commission {
-        marketplace_config.sale_commission_bps = commission;
+
+    // The remaining auction rules stay with the admin.
+    let updates_admin_fields = new_anti_snipe_window_seconds.is_some() ||
+        new_anti_snipe_extension_seconds.is_some() ||
+        new_min_bid_increment_usd_star.is_some() ||
+        new_min_bid_increment_bps.is_some() ||
+        new_refund_listing_fee_on_cancel.is_some() ||
+        new_max_auction_start_delay_seconds.is_some() ||
+        new_min_auction_duration_seconds.is_some() ||
+        new_max_auction_duration_seconds.is_some() ||
+        new_sealed_bid_slash_bps.is_some() ||
+        new_settlement_grace_period_seconds.is_some() ||
+        new_delivery_confirmation_period_seconds.is_some() ||
+        new_refund_listing_fee_on_admin_cancel.is_some();
+    if updates_admin_fields {
+        require_keys_eq!(authority, marketplace_config.admin, DubuuMarketplaceError::Unauthorized);
     }
     
-    if let Some(treasury) = new_treasury {
-        marketplace_config.treasury_pern_account = treasury;
+    if let Some(window) = new_anti_snipe_window_seconds {
+        require!(window >= 0, DubuuMarketplaceError::InvalidAntiSnipeSettings);
+        marketplace_config.anti_snipe_window_seconds = window;
     }
-    
-    if let Some(paused) = new_paused_status {
-        marketplace_config.is_paused = paused;
+
+    if let Some(extension) = new_anti_snipe_extension_seconds {
+        require!(extension >= 0, DubuuMarketplaceError::InvalidAntiSnipeSettings);
+        marketplace_config.anti_snipe_extension_seconds = extension;
     }
-    
-    if let Some(admin) = new_admin {
-        marketplace_config.admin = admin;
+
+    if let Some(increment) = new_min_bid_increment_usd_star {
+        marketplace_config.min_bid_increment_usd_star = increment;
+    }
+
+    if let Some(increment_bps) = new_min_bid_increment_bps {
+        require!(increment_bps <= 10000, DubuuMarketplaceError::InvalidBasisPoints);
+        marketplace_config.min_bid_increment_bps = increment_bps;
+    }
+
+    if let Some(refund_listing_fee) = new_refund_listing_fee_on_cancel {
+        marketplace_config.refund_listing_fee_on_cancel = refund_listing_fee;
+    }
+
+    if let Some(max_start_delay) = new_max_auction_start_delay_seconds {
+        require!(max_start_delay >= 0, DubuuMarketplaceError::InvalidAuctionStartTime);
+        marketplace_config.max_auction_start_delay_seconds = max_start_delay;
+    }
+
+    if let Some(min_duration) = new_min_auction_duration_seconds {
+        marketplace_config.min_auction_duration_seconds = min_duration;
+    }
+
+    if let Some(max_duration) = new_max_auction_duration_seconds {
+        marketplace_config.max_auction_duration_seconds = max_duration;
+    }
+
+    if let Some(slash_bps) = new_sealed_bid_slash_bps {
+        require!(slash_bps <= 10000, DubuuMarketplaceError::InvalidBasisPoints);
+        marketplace_config.sealed_bid_slash_bps = slash_bps;
+    }
+
+    if let Some(grace_period) = new_settlement_grace_period_seconds {
+        require!(grace_period >= 0, DubuuMarketplaceError::InvalidSettlementGracePeriod);
+        marketplace_config.settlement_grace_period_seconds = grace_period;
+    }
+
+    if let Some(delivery_period) = new_delivery_confirmation_period_seconds {
+        require!(delivery_period >= 0, DubuuMarketplaceError::InvalidDeliveryConfirmationPeriod);
+        marketplace_config.delivery_confirmation_period_seconds = delivery_period;
+    }
+
+    if let Some(refund_listing_fee) = new_refund_listing_fee_on_admin_cancel {
+        marketplace_config.refund_listing_fee_on_admin_cancel = refund_listing_fee;
     }
+
+    // Validate the bounds together so both can be moved in a single update.
+    require!(
+        marketplace_config.min_auction_duration_seconds > 0 &&
+        marketplace_config.min_auction_duration_seconds <= marketplace_config.max_auction_duration_seconds,
+        DubuuMarketplaceError::InvalidAuctionDurationBounds
+    );
     
     Ok(())
 }
+
+pub fn handle_grant_role(
+    ctx: Context<ManageRoleAccounts>,
+    role: MarketplaceRole,
+    holder: Pubkey,
+) -> Result<()> {
+    let role_registry = &mut ctx.accounts.role_registry;
+    require!(!internal_has_role(role_registry, role, holder), DubuuMarketplaceError::RoleAlreadyGranted);
+    require!(
+        role_registry.assignments.len() < MAX_ROLE_ASSIGNMENTS,
+        DubuuMarketplaceError::RoleRegistryFull
+    );
+    role_registry.assignments.push(RoleAssignment { role, holder });
+
+    emit!(RoleGrantedEvent {
+        role,
+        holder,
+        admin: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+pub fn handle_revoke_role(
+    ctx: Context<ManageRoleAccounts>,
+    role: MarketplaceRole,
+    holder: Pubkey,
+) -> Result<()> {
+    let role_registry = &mut ctx.accounts.role_registry;
+    let index = role_registry
+        .assignments
+        .iter()
+        .position(|assignment| assignment.role == role && assignment.holder == holder)
+        .ok_or(DubuuMarketplaceError::RoleNotGranted)?;
+    role_registry.assignments.remove(index);
+
+    emit!(RoleRevokedEvent {
+        role,
+        holder,
+        admin: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+// --- Internal Helper Functions ---
+
+pub fn internal_has_role(role_registry: &RoleRegistry, role: MarketplaceRole, holder: Pubkey) -> bool {
+    role_registry
+        .assignments
+        .iter()
+        .any(|assignment| assignment.role == role && assignment.holder == holder)
+}
+
+pub fn internal_require_role(role_registry: &RoleRegistry, role: MarketplaceRole, holder: Pubkey) -> Result<()> {
+    require!(internal_has_role(role_registry, role, holder), DubuuMarketplaceError::Unauthorized);
+    Ok(())
+}
+
+// The admin holds every role until ops grants them to dedicated keys and revokes its own.
+fn default_role_assignments(admin: Pubkey) -> Vec<RoleAssignment> {
+    [
+        MarketplaceRole::Verifier,
+        MarketplaceRole::FeeManager,
+        MarketplaceRole::Pauser,
+        MarketplaceRole::TreasuryManager,
+        MarketplaceRole::Arbiter,
+    ]
+    .into_iter()
+    .map(|role| RoleAssignment { role, holder: admin })
+    .collect()
+}
+
+// Tops up rent from the payer and grows a program-owned account to new_space, zeroing the new bytes.
+pub fn internal_grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_space: usize,
+) -> Result<()> {
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(new_space)
+        .saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        let cpi_accounts_rent = system_program::Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        system_program::transfer(
+            CpiContext::new(system_program.to_account_info(), cpi_accounts_rent),
+            rent_shortfall,
+        )?;
+    }
+    account.realloc(new_space, true)?;
+    Ok(())
+}
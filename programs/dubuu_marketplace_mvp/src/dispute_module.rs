@@ -0,0 +1,335 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Transfer, CloseAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::asset_module;
+use crate::auction_module;
+use crate::config_module;
+
+// Evidence and rulings live off-chain (Walrus); only their CIDs are stored here.
+const MAX_EVIDENCE_CID_LENGTH: usize = 100;
+
+// A buyer can dispute an AwaitingDelivery sale until its delivery deadline. Opening a
+// dispute moves the auction to Disputed, which freezes the escrowed proceeds (neither
+// confirm_delivery nor the crank will release them) until the arbiter rules.
+
+#[derive(Accounts)]
+pub struct OpenDisputeAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_status == AuctionProcessStatus::AwaitingDelivery @ DubuuMarketplaceError::InvalidAuctionStatus,
+        constraint = auction_account.highest_bidder == Some(buyer.key()) @ DubuuMarketplaceError::NotAuctionWinner
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + 32 + 32 + 32 + 8 + (4 + MAX_EVIDENCE_CID_LENGTH) + (4 + MAX_EVIDENCE_CID_LENGTH) + (4 + MAX_EVIDENCE_CID_LENGTH) + 1 + 2 + 8 + 1,
+        seeds = [b"dispute".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub dispute_account: Account<'info, DisputeAccount>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitDisputeEvidenceAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"dispute".as_ref(), dispute_account.auction_key.as_ref()],
+        bump = dispute_account.bump,
+        constraint = dispute_account.status == DisputeStatus::Open @ DubuuMarketplaceError::DisputeNotOpen
+    )]
+    pub dispute_account: Account<'info, DisputeAccount>,
+
+    // Either party to the sale.
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDisputeAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"dispute".as_ref(), auction_account.key().as_ref()],
+        bump = dispute_account.bump,
+        constraint = dispute_account.status == DisputeStatus::Open @ DubuuMarketplaceError::DisputeNotOpen
+    )]
+    pub dispute_account: Account<'info, DisputeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_status == AuctionProcessStatus::Disputed @ DubuuMarketplaceError::InvalidAuctionStatus
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        seeds = [b"role_registry".as_ref()],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+
+    #[account(
+        constraint = config_module::internal_has_role(&role_registry, MarketplaceRole::Arbiter, arbiter.key()) @ DubuuMarketplaceError::Unauthorized
+    )]
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = asset_account.key() == auction_account.asset_key @ DubuuMarketplaceError::InvalidAssetAccount
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA authority for the escrow account's tokens.
+    #[account(
+        seeds = [b"escrow_authority".as_ref(), auction_account.key().as_ref()],
+        bump = auction_account.escrow_authority_bump
+    )]
+    pub auction_escrow_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == dispute_account.seller @ DubuuMarketplaceError::InvalidTokenAccountOwner,
+        constraint = seller_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The disputing buyer; receives the escrow rent once it is emptied.
+    #[account(mut, address = dispute_account.buyer @ DubuuMarketplaceError::NotAuctionWinner)]
+    pub buyer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == dispute_account.buyer @ DubuuMarketplaceError::InvalidTokenAccountOwner,
+        constraint = buyer_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
+        constraint = treasury_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, token::Token>,
+}
+
+// --- Instruction Handlers ---
+
+pub fn handle_open_dispute(
+    ctx: Context<OpenDisputeAccounts>,
+    evidence_cid: String,
+) -> Result<()> {
+    require!(
+        evidence_cid.len() <= MAX_EVIDENCE_CID_LENGTH,
+        DubuuMarketplaceError::StringTooLong
+    );
+
+    let auction_account = &mut ctx.accounts.auction_account;
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < auction_account.delivery_deadline_timestamp,
+        DubuuMarketplaceError::DisputeWindowClosed
+    );
+
+    let dispute_account = &mut ctx.accounts.dispute_account;
+    dispute_account.auction_key = auction_account.key();
+    dispute_account.buyer = ctx.accounts.buyer.key();
+    dispute_account.seller = auction_account.seller;
+    dispute_account.opened_at_timestamp = clock.unix_timestamp;
+    dispute_account.buyer_evidence_cid = evidence_cid.clone();
+    dispute_account.seller_evidence_cid = String::new();
+    dispute_account.ruling_cid = String::new();
+    dispute_account.status = DisputeStatus::Open;
+    dispute_account.seller_share_bps = 0;
+    dispute_account.resolved_at_timestamp = 0;
+    dispute_account.bump = ctx.bumps.dispute_account;
+
+    auction_account.auction_status = AuctionProcessStatus::Disputed;
+
+    emit!(DisputeOpenedEvent {
+        dispute_key: dispute_account.key(),
+        auction_key: auction_account.key(),
+        buyer: dispute_account.buyer,
+        evidence_cid,
+    });
+
+    Ok(())
+}
+
+pub fn handle_submit_dispute_evidence(
+    ctx: Context<SubmitDisputeEvidenceAccounts>,
+    evidence_cid: String,
+) -> Result<()> {
+    require!(
+        evidence_cid.len() <= MAX_EVIDENCE_CID_LENGTH,
+        DubuuMarketplaceError::StringTooLong
+    );
+
+    let dispute_account = &mut ctx.accounts.dispute_account;
+    let submitter = ctx.accounts.submitter.key();
+
+    if submitter == dispute_account.buyer {
+        dispute_account.buyer_evidence_cid = evidence_cid.clone();
+    } else if submitter == dispute_account.seller {
+        dispute_account.seller_evidence_cid = evidence_cid.clone();
+    } else {
+        return err!(DubuuMarketplaceError::Unauthorized);
+    }
+
+    emit!(DisputeEvidenceSubmittedEvent {
+        dispute_key: dispute_account.key(),
+        submitter,
+        evidence_cid,
+    });
+
+    Ok(())
+}
+
+pub fn handle_resolve_dispute(
+    ctx: Context<ResolveDisputeAccounts>,
+    seller_share_bps: u16,
+    ruling_cid: String,
+) -> Result<()> {
+    require!(seller_share_bps <= 10000, DubuuMarketplaceError::InvalidBasisPoints);
+    require!(
+        ruling_cid.len() <= MAX_EVIDENCE_CID_LENGTH,
+        DubuuMarketplaceError::StringTooLong
+    );
+
+    let auction_account = &mut ctx.accounts.auction_account;
+
+    // 10000 releases everything to the seller, 0 refunds the buyer in full, anything else splits.
+    let sale_amount = auction_account.clearing_price_usd_star;
+    let seller_gross = (sale_amount as u128)
+        .checked_mul(seller_share_bps as u128)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?
+        / 10000;
+    let seller_gross = seller_gross as u64;
+    let buyer_refund = sale_amount
+        .checked_sub(seller_gross)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    // Commission is only taken on the part the seller actually receives.
+    let commission = auction_module::internal_sale_commission(seller_gross, auction_account.sale_commission_bps)?;
+    let amount_to_seller = seller_gross
+        .checked_sub(commission)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    let auction_key_as_bytes = auction_account.key().to_bytes();
+    let escrow_authority_seeds_slices: &[&[u8]] = &[
+        b"escrow_authority".as_ref(),
+        auction_key_as_bytes.as_ref(),
+        &[auction_account.escrow_authority_bump],
+    ];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    if amount_to_seller > 0 {
+        let cpi_accounts_to_seller = Transfer {
+            from: ctx.accounts.auction_escrow_token_account.to_account_info(),
+            to: ctx.accounts.seller_token_account.to_account_info(),
+            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_to_seller = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_to_seller,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_to_seller, amount_to_seller)?;
+    }
+
+    if commission > 0 {
+        let cpi_accounts_to_treasury = Transfer {
+            from: ctx.accounts.auction_escrow_token_account.to_account_info(),
+            to: ctx.accounts.treasury_pern_token_account.to_account_info(),
+            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_to_treasury = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_to_treasury,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_to_treasury, commission)?;
+    }
+
+    if buyer_refund > 0 {
+        let cpi_accounts_to_buyer = Transfer {
+            from: ctx.accounts.auction_escrow_token_account.to_account_info(),
+            to: ctx.accounts.buyer_token_account.to_account_info(),
+            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_to_buyer = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_to_buyer,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_to_buyer, buyer_refund)?;
+    }
+
+    // Losing bids may still sit in escrow until their owners withdraw them.
+    ctx.accounts.auction_escrow_token_account.reload()?;
+    if ctx.accounts.auction_escrow_token_account.amount == 0 {
+        let cpi_accounts_close_escrow = CloseAccount {
+            account: ctx.accounts.auction_escrow_token_account.to_account_info(),
+            destination: ctx.accounts.buyer.to_account_info(),
+            authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_close_escrow = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_close_escrow,
+            signer_seeds,
+        );
+        token::close_account(cpi_ctx_close_escrow)?;
+    }
+
+    // A full refund unwinds the sale; any payout to the seller completes it. Either way the sale
+    // funds are fully distributed and the winner has nothing left to withdraw.
+    let asset_account = &mut ctx.accounts.asset_account;
+    if seller_share_bps == 0 {
+        asset_module::internal_return_asset_to_ready_for_auction(asset_account, auction_account.key())?;
+        auction_account.auction_status = AuctionProcessStatus::Refunded;
+    } else {
+        asset_module::internal_transfer_ownership(asset_account, ctx.accounts.buyer.key())?;
+        auction_account.auction_status = AuctionProcessStatus::Completed;
+    }
+
+    let dispute_account = &mut ctx.accounts.dispute_account;
+    dispute_account.status = DisputeStatus::Resolved;
+    dispute_account.seller_share_bps = seller_share_bps;
+    dispute_account.ruling_cid = ruling_cid.clone();
+    dispute_account.resolved_at_timestamp = Clock::get()?.unix_timestamp;
+
+    emit!(DisputeResolvedEvent {
+        dispute_key: dispute_account.key(),
+        auction_key: auction_account.key(),
+        arbiter: ctx.accounts.arbiter.key(),
+        seller_share_bps,
+        amount_to_seller,
+        buyer_refund,
+        ruling_cid,
+    });
+
+    Ok(())
+}
@@ -81,4 +81,169 @@ pub enum DubuuMarketplaceError {
 
     #[msg("Invalid seller account for rent")]
     InvalidSellerAccountForRent,
+
+    #[msg("Reserve price must be at least the auction start price")]
+    InvalidReservePrice,
+
+    #[msg("Token account for refunding the highest bidder was not provided")]
+    MissingHighestBidderRefundAccount,
+
+    #[msg("Anti-sniping window and extension must not be negative")]
+    InvalidAntiSnipeSettings,
+
+    #[msg("Bid does not meet the minimum increment over the current highest bid")]
+    BidIncrementTooLow,
+
+    #[msg("Basis point value exceeds 10000")]
+    InvalidBasisPoints,
+
+    #[msg("Buy-it-now price must exceed the start price and be at least the reserve price")]
+    InvalidBuyNowPrice,
+
+    #[msg("Auction already has bids and can no longer be cancelled by the seller")]
+    AuctionHasBids,
+
+    #[msg("Auction start time must not be in the past")]
+    InvalidAuctionStartTime,
+
+    #[msg("Auction start time is further in the future than the marketplace allows")]
+    AuctionStartTooFarInFuture,
+
+    #[msg("Auction has not started yet")]
+    AuctionNotStarted,
+
+    #[msg("Auction duration is shorter than the marketplace minimum")]
+    AuctionDurationTooShort,
+
+    #[msg("Auction duration is longer than the marketplace maximum")]
+    AuctionDurationTooLong,
+
+    #[msg("Minimum auction duration must be positive and not exceed the maximum")]
+    InvalidAuctionDurationBounds,
+
+    #[msg("Operation is not supported for this auction type")]
+    InvalidAuctionType,
+
+    #[msg("Dutch auction floor price must be below the start price")]
+    InvalidDutchPriceRange,
+
+    #[msg("Dutch auction price step must be between zero and the auction duration")]
+    InvalidDutchPriceStep,
+
+    #[msg("Sealed-bid deposit must be greater than zero")]
+    InvalidSealedBidDeposit,
+
+    #[msg("Reveal phase duration must be positive and within the maximum auction duration")]
+    InvalidRevealDuration,
+
+    #[msg("Sealed-bid commit phase has ended")]
+    CommitPhaseEnded,
+
+    #[msg("Sealed-bid reveal phase is not open")]
+    RevealPhaseNotActive,
+
+    #[msg("Sealed bid has already been revealed")]
+    BidAlreadyRevealed,
+
+    #[msg("Revealed amount and salt do not match the committed hash")]
+    InvalidBidReveal,
+
+    #[msg("Bid funds cannot be withdrawn until the auction has been finalized or cancelled")]
+    WithdrawalNotAvailable,
+
+    #[msg("Bid record does not belong to this auction or bidder")]
+    InvalidBidRecord,
+
+    #[msg("Auction escrow account is required to return the escrowed funds")]
+    MissingEscrowAccount,
+
+    #[msg("Settlement grace period cannot be negative")]
+    InvalidSettlementGracePeriod,
+
+    #[msg("Only the winner can settle before the settlement deadline")]
+    SettlementDeadlineNotReached,
+
+    #[msg("Delivery confirmation period cannot be negative")]
+    InvalidDeliveryConfirmationPeriod,
+
+    #[msg("Buyer can still confirm delivery; funds are not released yet")]
+    DeliveryWindowOpen,
+
+    #[msg("Disputes can only be opened before the delivery deadline")]
+    DisputeWindowClosed,
+
+    #[msg("Dispute is not open")]
+    DisputeNotOpen,
+
+    #[msg("Fixed price must be greater than zero")]
+    InvalidFixedPrice,
+
+    #[msg("Listing price changed since the buyer last saw it")]
+    FixedPriceChanged,
+
+    #[msg("Seller cannot buy their own listing")]
+    CannotBuyOwnListing,
+
+    #[msg("Offer amount must be greater than zero")]
+    InvalidOfferAmount,
+
+    #[msg("Offer expiry must be in the future")]
+    InvalidOfferExpiry,
+
+    #[msg("Offer has expired")]
+    OfferExpired,
+
+    #[msg("Only the buyer can cancel an offer before it expires")]
+    OfferNotExpired,
+
+    #[msg("Owner cannot make an offer on their own asset")]
+    CannotOfferOnOwnAsset,
+
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+
+    #[msg("Account already holds this role")]
+    RoleAlreadyGranted,
+
+    #[msg("Account does not hold this role")]
+    RoleNotGranted,
+
+    #[msg("Role registry is full")]
+    RoleRegistryFull,
+
+    #[msg("Signer is not a configured config signer")]
+    NotConfigSigner,
+
+    #[msg("Config signer set or approval threshold is invalid")]
+    InvalidConfigSigners,
+
+    #[msg("Proposal expiry must be in the future and within the maximum proposal lifetime")]
+    InvalidProposalExpiry,
+
+    #[msg("Config proposal has expired")]
+    ProposalExpired,
+
+    #[msg("Only the proposer can cancel a config proposal before it expires")]
+    ProposalNotExpired,
+
+    #[msg("Signer has already approved this proposal")]
+    ProposalAlreadyApproved,
+
+    #[msg("Config proposal does not have enough approvals")]
+    InsufficientApprovals,
+
+    #[msg("Config change effective time must respect the configured minimum delay")]
+    InvalidEffectiveTime,
+
+    #[msg("Config change is not yet effective")]
+    ConfigChangeNotYetEffective,
+
+    #[msg("Config change delay must not be negative")]
+    InvalidConfigChangeDelay,
+
+    #[msg("Listing fee can only be collected once the auction can no longer be cancelled")]
+    ListingFeeNotCollectable,
+
+    #[msg("Account is not in the legacy layout this migration expects")]
+    AccountNotMigratable,
 }
\ No newline at end of file
@@ -0,0 +1,280 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::asset_module;
+use crate::auction_module;
+
+// Fixed-price listings sell an asset outright: the buyer pays the seller (less commission)
+// in the same transaction, so unlike auctions no escrow is needed. One listing per asset;
+// the account is closed when the asset sells or is delisted.
+
+#[derive(Accounts)]
+pub struct ListFixedPriceAccounts<'info> {
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 2,
+        seeds = [b"fixed_price_listing".as_ref(), asset_account.key().as_ref()],
+        bump
+    )]
+    pub fixed_price_listing: Account<'info, FixedPriceListing>,
+
+    #[account(
+        mut,
+        constraint = asset_account.current_owner == seller.key() @ DubuuMarketplaceError::Unauthorized,
+        constraint = asset_account.ownership_verification_status == OwnershipStatus::Verified @ DubuuMarketplaceError::OwnershipVerificationRequired,
+        constraint = asset_account.asset_listed_status == AssetListedStatus::ReadyForAuction ||
+            asset_account.asset_listed_status == AssetListedStatus::Sold @ DubuuMarketplaceError::AssetNotReadyForAuction
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump,
+        constraint = !marketplace_config.is_paused @ DubuuMarketplaceError::MarketplacePaused
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        mut,
+        constraint = seller_pern_token_account.mint == marketplace_config.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = seller_pern_token_account.owner == seller.key() @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub seller_pern_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
+        constraint = treasury_pern_token_account.mint == marketplace_config.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuyFixedPriceAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"fixed_price_listing".as_ref(), fixed_price_listing.asset_key.as_ref()],
+        bump = fixed_price_listing.bump,
+        close = seller
+    )]
+    pub fixed_price_listing: Account<'info, FixedPriceListing>,
+
+    #[account(
+        mut,
+        constraint = asset_account.key() == fixed_price_listing.asset_key @ DubuuMarketplaceError::InvalidAssetAccount,
+        constraint = asset_account.current_owner == fixed_price_listing.seller @ DubuuMarketplaceError::Unauthorized,
+        constraint = asset_account.ownership_verification_status == OwnershipStatus::Verified @ DubuuMarketplaceError::OwnershipVerificationRequired,
+        constraint = asset_account.asset_listed_status == AssetListedStatus::ListedFixedPrice @ DubuuMarketplaceError::InvalidAssetStatus
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = buyer_pern_token_account.mint == fixed_price_listing.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = buyer_pern_token_account.owner == buyer.key() @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub buyer_pern_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Seller of the listing; receives the listing account's rent.
+    #[account(mut, address = fixed_price_listing.seller @ DubuuMarketplaceError::InvalidSellerAccountForRent)]
+    pub seller: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == fixed_price_listing.seller @ DubuuMarketplaceError::InvalidTokenAccountOwner,
+        constraint = seller_token_account.mint == fixed_price_listing.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump,
+        constraint = !marketplace_config.is_paused @ DubuuMarketplaceError::MarketplacePaused
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
+        constraint = treasury_pern_token_account.mint == fixed_price_listing.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFixedPriceAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"fixed_price_listing".as_ref(), fixed_price_listing.asset_key.as_ref()],
+        bump = fixed_price_listing.bump,
+        has_one = seller @ DubuuMarketplaceError::Unauthorized
+    )]
+    pub fixed_price_listing: Account<'info, FixedPriceListing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DelistFixedPriceAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"fixed_price_listing".as_ref(), fixed_price_listing.asset_key.as_ref()],
+        bump = fixed_price_listing.bump,
+        has_one = seller @ DubuuMarketplaceError::Unauthorized,
+        close = seller
+    )]
+    pub fixed_price_listing: Account<'info, FixedPriceListing>,
+
+    #[account(
+        mut,
+        constraint = asset_account.key() == fixed_price_listing.asset_key @ DubuuMarketplaceError::InvalidAssetAccount
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+}
+
+// --- Instruction Handlers ---
+
+pub fn handle_list_fixed_price(
+    ctx: Context<ListFixedPriceAccounts>,
+    price_usd_star: u64,
+) -> Result<()> {
+    require!(price_usd_star > 0, DubuuMarketplaceError::InvalidFixedPrice);
+
+    let marketplace_config = &ctx.accounts.marketplace_config;
+    auction_module::internal_collect_listing_fee(
+        ctx.accounts.seller_pern_token_account.to_account_info(),
+        ctx.accounts.treasury_pern_token_account.to_account_info(),
+        ctx.accounts.seller.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        marketplace_config.listing_fee_usd_star,
+    )?;
+
+    let fixed_price_listing = &mut ctx.accounts.fixed_price_listing;
+    let asset_account = &mut ctx.accounts.asset_account;
+
+    fixed_price_listing.asset_key = asset_account.key();
+    fixed_price_listing.seller = ctx.accounts.seller.key();
+    fixed_price_listing.pern_usd_star_mint = marketplace_config.pern_usd_star_mint;
+    fixed_price_listing.price_usd_star = price_usd_star;
+    fixed_price_listing.listing_fee_paid_usd_star = marketplace_config.listing_fee_usd_star;
+    fixed_price_listing.listed_at_timestamp = Clock::get()?.unix_timestamp;
+    fixed_price_listing.bump = ctx.bumps.fixed_price_listing;
+    fixed_price_listing.sale_commission_bps = marketplace_config.sale_commission_bps;
+
+    asset_module::internal_update_asset_status_to_listed_fixed_price(asset_account)?;
+
+    emit!(FixedPriceListedEvent {
+        listing_key: fixed_price_listing.key(),
+        asset_key: asset_account.key(),
+        seller: fixed_price_listing.seller,
+        price: price_usd_star,
+    });
+
+    Ok(())
+}
+
+pub fn handle_buy_fixed_price(
+    ctx: Context<BuyFixedPriceAccounts>,
+    expected_price_usd_star: u64,
+) -> Result<()> {
+    let fixed_price_listing = &ctx.accounts.fixed_price_listing;
+    let price = fixed_price_listing.price_usd_star;
+
+    // Guards against the seller raising the price while the purchase is in flight.
+    require!(price == expected_price_usd_star, DubuuMarketplaceError::FixedPriceChanged);
+    require_keys_neq!(ctx.accounts.buyer.key(), fixed_price_listing.seller, DubuuMarketplaceError::CannotBuyOwnListing);
+
+    let commission = auction_module::internal_sale_commission(
+        price,
+        fixed_price_listing.sale_commission_bps,
+    )?;
+    let amount_to_seller = price
+        .checked_sub(commission)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    if amount_to_seller > 0 {
+        let cpi_accounts_to_seller = Transfer {
+            from: ctx.accounts.buyer_pern_token_account.to_account_info(),
+            to: ctx.accounts.seller_token_account.to_account_info(),
+            authority: ctx.accounts.buyer.to_account_info(),
+        };
+        let cpi_ctx_to_seller = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_to_seller,
+        );
+        token::transfer(cpi_ctx_to_seller, amount_to_seller)?;
+    }
+
+    if commission > 0 {
+        let cpi_accounts_to_treasury = Transfer {
+            from: ctx.accounts.buyer_pern_token_account.to_account_info(),
+            to: ctx.accounts.treasury_pern_token_account.to_account_info(),
+            authority: ctx.accounts.buyer.to_account_info(),
+        };
+        let cpi_ctx_to_treasury = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_to_treasury,
+        );
+        token::transfer(cpi_ctx_to_treasury, commission)?;
+    }
+
+    asset_module::internal_transfer_ownership(
+        &mut ctx.accounts.asset_account,
+        ctx.accounts.buyer.key()
+    )?;
+
+    emit!(FixedPriceSoldEvent {
+        listing_key: fixed_price_listing.key(),
+        asset_key: fixed_price_listing.asset_key,
+        buyer: ctx.accounts.buyer.key(),
+        price,
+    });
+
+    Ok(())
+}
+
+pub fn handle_update_price(
+    ctx: Context<UpdateFixedPriceAccounts>,
+    new_price_usd_star: u64,
+) -> Result<()> {
+    require!(new_price_usd_star > 0, DubuuMarketplaceError::InvalidFixedPrice);
+
+    let fixed_price_listing = &mut ctx.accounts.fixed_price_listing;
+    let old_price = fixed_price_listing.price_usd_star;
+    fixed_price_listing.price_usd_star = new_price_usd_star;
+
+    emit!(FixedPriceUpdatedEvent {
+        listing_key: fixed_price_listing.key(),
+        old_price,
+        new_price: new_price_usd_star,
+    });
+
+    Ok(())
+}
+
+pub fn handle_delist(ctx: Context<DelistFixedPriceAccounts>) -> Result<()> {
+    // The listing fee is not refunded; the listing account's rent goes back to the seller.
+    asset_module::internal_return_asset_from_fixed_price_listing(&mut ctx.accounts.asset_account)?;
+
+    emit!(FixedPriceDelistedEvent {
+        listing_key: ctx.accounts.fixed_price_listing.key(),
+        asset_key: ctx.accounts.asset_account.key(),
+    });
+
+    Ok(())
+}
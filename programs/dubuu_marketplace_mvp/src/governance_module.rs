@@ -0,0 +1,523 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::config_module;
+
+// Sensitive config changes (fees, commission, treasury, admin and the signer set itself) are stored as
+// ConfigProposal PDAs and only applied once config_approval_threshold of the config_signers have
+// approved them and the effective time has passed. The effective time is at least
+// config_change_delay_seconds out, so listings and users get warning before fees move.
+// Proposals that are not executed before their expiry can no longer be applied.
+// The admin handover is an Admin proposal (propose_admin is shorthand for one) followed by
+// accept_admin from the successor.
+
+pub const MAX_CONFIG_PROPOSAL_LIFETIME_SECONDS: i64 = 7 * 24 * 60 * 60; // 7 days
+
+#[derive(Accounts)]
+pub struct ProposeConfigChangeAccounts<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + 8 + 32 + (1 + (4 + MAX_CONFIG_SIGNERS * 32) + 1) + (4 + MAX_CONFIG_SIGNERS * 32) + 8 + 8 + 1 + 8,
+        seeds = [b"config_proposal".as_ref(), &marketplace_config.config_proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub config_proposal: Account<'info, ConfigProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        seeds = [b"role_registry".as_ref()],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveConfigChangeAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"config_proposal".as_ref(), &config_proposal.proposal_id.to_le_bytes()],
+        bump = config_proposal.bump
+    )]
+    pub config_proposal: Account<'info, ConfigProposal>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump,
+        constraint = marketplace_config.config_signers.contains(&signer.key()) @ DubuuMarketplaceError::NotConfigSigner
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChangeAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"config_proposal".as_ref(), &config_proposal.proposal_id.to_le_bytes()],
+        bump = config_proposal.bump,
+        close = proposer
+    )]
+    pub config_proposal: Account<'info, ConfigProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    /// CHECK: Creator of the proposal; receives its rent. Anyone may execute an approved proposal.
+    #[account(mut, address = config_proposal.proposer @ DubuuMarketplaceError::Unauthorized)]
+    pub proposer: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigChangeAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"config_proposal".as_ref(), &config_proposal.proposal_id.to_le_bytes()],
+        bump = config_proposal.bump,
+        close = proposer
+    )]
+    pub config_proposal: Account<'info, ConfigProposal>,
+
+    /// CHECK: Creator of the proposal; receives its rent. Anyone may clean up an expired proposal.
+    #[account(mut, address = config_proposal.proposer @ DubuuMarketplaceError::Unauthorized)]
+    pub proposer: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdminAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump,
+        constraint = marketplace_config.pending_admin.is_some() @ DubuuMarketplaceError::NoPendingAdmin,
+        constraint = marketplace_config.pending_admin == Some(pending_admin.key()) @ DubuuMarketplaceError::Unauthorized
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"role_registry".as_ref()],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+
+    pub pending_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminTransferAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump,
+        has_one = admin @ DubuuMarketplaceError::Unauthorized
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+// --- Instruction Handlers ---
+
+pub fn handle_propose_config_change(
+    ctx: Context<ProposeConfigChangeAccounts>,
+    change: ConfigChange,
+    effective_at_timestamp: i64,
+    expiry_timestamp: i64,
+) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let marketplace_config = &mut ctx.accounts.marketplace_config;
+
+    let earliest_effective_timestamp = current_timestamp
+        .checked_add(marketplace_config.config_change_delay_seconds)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+    require!(
+        effective_at_timestamp >= earliest_effective_timestamp,
+        DubuuMarketplaceError::InvalidEffectiveTime
+    );
+    // The lifetime counts from the effective time, so long delays still leave a window to execute.
+    let max_expiry_timestamp = effective_at_timestamp
+        .checked_add(MAX_CONFIG_PROPOSAL_LIFETIME_SECONDS)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+    require!(
+        expiry_timestamp > effective_at_timestamp && expiry_timestamp <= max_expiry_timestamp,
+        DubuuMarketplaceError::InvalidProposalExpiry
+    );
+
+    let proposer = ctx.accounts.proposer.key();
+    let is_config_signer = marketplace_config.config_signers.contains(&proposer);
+
+    // Role holders may put forward changes to the field they manage; everything else needs a config signer.
+    match &change {
+        ConfigChange::ListingFee { .. } => {
+            if !is_config_signer {
+                config_module::internal_require_role(&ctx.accounts.role_registry, MarketplaceRole::FeeManager, proposer)?;
+            }
+        }
+        ConfigChange::KeeperFee { keeper_fee_bps } => {
+            require!(*keeper_fee_bps <= 10000, DubuuMarketplaceError::InvalidBasisPoints);
+            if !is_config_signer {
+                config_module::internal_require_role(&ctx.accounts.role_registry, MarketplaceRole::FeeManager, proposer)?;
+            }
+        }
+        ConfigChange::SaleCommission { sale_commission_bps } => {
+            require!(*sale_commission_bps <= 10000, DubuuMarketplaceError::InvalidBasisPoints);
+            if !is_config_signer {
+                config_module::internal_require_role(&ctx.accounts.role_registry, MarketplaceRole::FeeManager, proposer)?;
+            }
+        }
+        ConfigChange::Treasury { .. } => {
+            if !is_config_signer {
+                config_module::internal_require_role(&ctx.accounts.role_registry, MarketplaceRole::TreasuryManager, proposer)?;
+            }
+        }
+        ConfigChange::Admin { .. } => {
+            require!(is_config_signer, DubuuMarketplaceError::NotConfigSigner);
+        }
+        ConfigChange::ConfigSigners { signers, threshold } => {
+            require!(is_config_signer, DubuuMarketplaceError::NotConfigSigner);
+            validate_config_signers(signers, *threshold)?;
+        }
+        ConfigChange::ChangeDelay { config_change_delay_seconds } => {
+            require!(is_config_signer, DubuuMarketplaceError::NotConfigSigner);
+            require!(*config_change_delay_seconds >= 0, DubuuMarketplaceError::InvalidConfigChangeDelay);
+        }
+    }
+
+    let config_proposal = &mut ctx.accounts.config_proposal;
+    config_proposal.proposal_id = marketplace_config.config_proposal_count;
+    config_proposal.proposer = proposer;
+    config_proposal.change = change.clone();
+    // A proposing config signer counts as the first approval.
+    config_proposal.approvals = if is_config_signer { vec![proposer] } else { Vec::new() };
+    config_proposal.created_at_timestamp = current_timestamp;
+    config_proposal.expiry_timestamp = expiry_timestamp;
+    config_proposal.bump = ctx.bumps.config_proposal;
+    config_proposal.effective_at_timestamp = effective_at_timestamp;
+
+    marketplace_config.config_proposal_count = marketplace_config
+        .config_proposal_count
+        .checked_add(1)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    emit!(ConfigChangeProposedEvent {
+        proposal_key: config_proposal.key(),
+        proposal_id: config_proposal.proposal_id,
+        proposer,
+        change,
+        effective_at_timestamp,
+        expiry_timestamp,
+    });
+
+    if config_proposal.approvals.len() >= marketplace_config.config_approval_threshold as usize {
+        emit!(ConfigChangeQueuedEvent {
+            proposal_key: config_proposal.key(),
+            change: config_proposal.change.clone(),
+            effective_at_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+/// First step of the admin handover: an `Admin` proposal that takes effect as soon as the timelock
+/// allows and stays executable for the longest permitted lifetime.
+pub fn handle_propose_admin(ctx: Context<ProposeConfigChangeAccounts>, proposed_admin: Pubkey) -> Result<()> {
+    let effective_at_timestamp = Clock::get()?
+        .unix_timestamp
+        .checked_add(ctx.accounts.marketplace_config.config_change_delay_seconds)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+    let expiry_timestamp = effective_at_timestamp
+        .checked_add(MAX_CONFIG_PROPOSAL_LIFETIME_SECONDS)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    handle_propose_config_change(
+        ctx,
+        ConfigChange::Admin { proposed_admin },
+        effective_at_timestamp,
+        expiry_timestamp,
+    )
+}
+
+pub fn handle_approve_config_change(ctx: Context<ApproveConfigChangeAccounts>) -> Result<()> {
+    let config_proposal = &mut ctx.accounts.config_proposal;
+    let signer = ctx.accounts.signer.key();
+
+    require!(
+        Clock::get()?.unix_timestamp < config_proposal.expiry_timestamp,
+        DubuuMarketplaceError::ProposalExpired
+    );
+    require!(
+        !config_proposal.approvals.contains(&signer),
+        DubuuMarketplaceError::ProposalAlreadyApproved
+    );
+    // Approvals from signers who were since removed are dropped so the list cannot outgrow its space.
+    let config_signers = &ctx.accounts.marketplace_config.config_signers;
+    config_proposal.approvals.retain(|approver| config_signers.contains(approver));
+    config_proposal.approvals.push(signer);
+
+    emit!(ConfigChangeApprovedEvent {
+        proposal_key: config_proposal.key(),
+        signer,
+        approval_count: config_proposal.approvals.len() as u8,
+    });
+
+    // Announce the change the moment it crosses the threshold; later approvals don't re-announce it.
+    if config_proposal.approvals.len() == ctx.accounts.marketplace_config.config_approval_threshold as usize {
+        emit!(ConfigChangeQueuedEvent {
+            proposal_key: config_proposal.key(),
+            change: config_proposal.change.clone(),
+            effective_at_timestamp: config_proposal.effective_at_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn handle_execute_config_change(ctx: Context<ExecuteConfigChangeAccounts>) -> Result<()> {
+    let config_proposal = &ctx.accounts.config_proposal;
+    let marketplace_config = &mut ctx.accounts.marketplace_config;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        current_timestamp >= config_proposal.effective_at_timestamp,
+        DubuuMarketplaceError::ConfigChangeNotYetEffective
+    );
+    require!(
+        current_timestamp < config_proposal.expiry_timestamp,
+        DubuuMarketplaceError::ProposalExpired
+    );
+    // Only approvals from the current signer set count.
+    let approval_count = config_proposal
+        .approvals
+        .iter()
+        .filter(|approver| marketplace_config.config_signers.contains(approver))
+        .count();
+    require!(
+        approval_count >= marketplace_config.config_approval_threshold as usize,
+        DubuuMarketplaceError::InsufficientApprovals
+    );
+
+    match &config_proposal.change {
+        ConfigChange::ListingFee { listing_fee_usd_star } => {
+            marketplace_config.listing_fee_usd_star = *listing_fee_usd_star;
+        }
+        ConfigChange::KeeperFee { keeper_fee_bps } => {
+            marketplace_config.keeper_fee_bps = *keeper_fee_bps;
+        }
+        ConfigChange::SaleCommission { sale_commission_bps } => {
+            marketplace_config.sale_commission_bps = *sale_commission_bps;
+        }
+        ConfigChange::Treasury { treasury_pern_account } => {
+            marketplace_config.treasury_pern_account = *treasury_pern_account;
+        }
+        ConfigChange::Admin { proposed_admin } => {
+            // The new admin still has to sign accept_admin before it takes over.
+            marketplace_config.pending_admin = Some(*proposed_admin);
+            emit!(AdminTransferProposedEvent {
+                admin: marketplace_config.admin,
+                proposed_admin: *proposed_admin,
+            });
+        }
+        ConfigChange::ConfigSigners { signers, threshold } => {
+            marketplace_config.config_signers = signers.clone();
+            marketplace_config.config_approval_threshold = *threshold;
+        }
+        ConfigChange::ChangeDelay { config_change_delay_seconds } => {
+            marketplace_config.config_change_delay_seconds = *config_change_delay_seconds;
+        }
+    }
+
+    emit!(ConfigChangeExecutedEvent {
+        proposal_key: config_proposal.key(),
+        change: config_proposal.change.clone(),
+    });
+
+    Ok(())
+}
+
+pub fn handle_cancel_config_change(ctx: Context<CancelConfigChangeAccounts>) -> Result<()> {
+    let config_proposal = &ctx.accounts.config_proposal;
+    let expired = Clock::get()?.unix_timestamp >= config_proposal.expiry_timestamp;
+    require!(
+        expired || ctx.accounts.signer.key() == config_proposal.proposer,
+        DubuuMarketplaceError::ProposalNotExpired
+    );
+
+    emit!(ConfigChangeCancelledEvent {
+        proposal_key: config_proposal.key(),
+        expired,
+    });
+
+    Ok(())
+}
+
+// Second step of an executed Admin proposal. The outgoing admin's roles and config signer seat
+// move to the successor, so the previous key keeps no residual powers.
+pub fn handle_accept_admin(ctx: Context<AcceptAdminAccounts>) -> Result<()> {
+    let marketplace_config = &mut ctx.accounts.marketplace_config;
+    let previous_admin = marketplace_config.admin;
+    let new_admin = ctx.accounts.pending_admin.key();
+
+    hand_over_roles(&mut ctx.accounts.role_registry.assignments, previous_admin, new_admin);
+    let threshold = marketplace_config.config_approval_threshold;
+    hand_over_signer_seat(&mut marketplace_config.config_signers, threshold, previous_admin, new_admin)?;
+
+    marketplace_config.admin = new_admin;
+    marketplace_config.pending_admin = None;
+
+    emit!(AdminTransferAcceptedEvent {
+        previous_admin,
+        new_admin,
+    });
+
+    Ok(())
+}
+
+pub fn handle_cancel_admin_transfer(ctx: Context<CancelAdminTransferAccounts>) -> Result<()> {
+    let marketplace_config = &mut ctx.accounts.marketplace_config;
+    let cancelled_admin = marketplace_config
+        .pending_admin
+        .take()
+        .ok_or(DubuuMarketplaceError::NoPendingAdmin)?;
+
+    emit!(AdminTransferCancelledEvent {
+        admin: marketplace_config.admin,
+        cancelled_admin,
+    });
+
+    Ok(())
+}
+
+// --- Internal Helper Functions ---
+
+fn validate_config_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_CONFIG_SIGNERS,
+        DubuuMarketplaceError::InvalidConfigSigners
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        DubuuMarketplaceError::InvalidConfigSigners
+    );
+    let has_duplicates = signers
+        .iter()
+        .enumerate()
+        .any(|(index, signer)| signers[..index].contains(signer));
+    require!(!has_duplicates, DubuuMarketplaceError::InvalidConfigSigners);
+    Ok(())
+}
+
+fn hand_over_roles(assignments: &mut Vec<RoleAssignment>, from: Pubkey, to: Pubkey) {
+    for assignment in assignments.iter_mut() {
+        if assignment.holder == from {
+            assignment.holder = to;
+        }
+    }
+    // Drop roles the successor already held so each assignment stays unique.
+    let mut seen: Vec<RoleAssignment> = Vec::with_capacity(assignments.len());
+    assignments.retain(|assignment| {
+        if seen.contains(assignment) {
+            false
+        } else {
+            seen.push(assignment.clone());
+            true
+        }
+    });
+}
+
+fn hand_over_signer_seat(signers: &mut Vec<Pubkey>, threshold: u8, from: Pubkey, to: Pubkey) -> Result<()> {
+    if from == to {
+        return Ok(());
+    }
+    if signers.contains(&to) {
+        signers.retain(|signer| *signer != from);
+    } else if let Some(seat) = signers.iter_mut().find(|signer| **signer == from) {
+        *seat = to;
+    }
+    // A successor who already held a seat shrinks the set; it must still meet the threshold.
+    validate_config_signers(signers, threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_signers_accept_a_valid_set() {
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        assert!(validate_config_signers(&signers, 1).is_ok());
+        assert!(validate_config_signers(&signers, 3).is_ok());
+    }
+
+    #[test]
+    fn config_signers_reject_bad_thresholds() {
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        assert!(validate_config_signers(&signers, 0).is_err());
+        assert!(validate_config_signers(&signers, 3).is_err());
+    }
+
+    #[test]
+    fn config_signers_reject_empty_oversized_and_duplicate_sets() {
+        assert!(validate_config_signers(&[], 1).is_err());
+        let too_many: Vec<Pubkey> = (0..=MAX_CONFIG_SIGNERS).map(|_| Pubkey::new_unique()).collect();
+        assert!(validate_config_signers(&too_many, 1).is_err());
+        let signer = Pubkey::new_unique();
+        assert!(validate_config_signers(&[signer, Pubkey::new_unique(), signer], 2).is_err());
+    }
+
+    #[test]
+    fn admin_handover_moves_roles_without_duplicates() {
+        let (old_admin, new_admin, verifier) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut assignments = vec![
+            RoleAssignment { role: MarketplaceRole::Pauser, holder: old_admin },
+            RoleAssignment { role: MarketplaceRole::Arbiter, holder: old_admin },
+            RoleAssignment { role: MarketplaceRole::Arbiter, holder: new_admin },
+            RoleAssignment { role: MarketplaceRole::Verifier, holder: verifier },
+        ];
+        hand_over_roles(&mut assignments, old_admin, new_admin);
+        assert_eq!(
+            assignments,
+            vec![
+                RoleAssignment { role: MarketplaceRole::Pauser, holder: new_admin },
+                RoleAssignment { role: MarketplaceRole::Arbiter, holder: new_admin },
+                RoleAssignment { role: MarketplaceRole::Verifier, holder: verifier },
+            ]
+        );
+    }
+
+    #[test]
+    fn admin_handover_replaces_the_signer_seat() {
+        let (old_admin, new_admin, cosigner) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut signers = vec![old_admin, cosigner];
+        hand_over_signer_seat(&mut signers, 2, old_admin, new_admin).unwrap();
+        assert_eq!(signers, vec![new_admin, cosigner]);
+
+        // A successor who already held a seat keeps one; the set must still meet the threshold.
+        let mut signers = vec![old_admin, new_admin, cosigner];
+        hand_over_signer_seat(&mut signers, 2, old_admin, new_admin).unwrap();
+        assert_eq!(signers, vec![new_admin, cosigner]);
+        let mut signers = vec![old_admin, new_admin];
+        assert!(hand_over_signer_seat(&mut signers, 2, old_admin, new_admin).is_err());
+    }
+}
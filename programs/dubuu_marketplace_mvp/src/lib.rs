@@ -3,17 +3,27 @@ use anchor_lang::prelude::*;
 pub mod config_module;
 pub mod asset_module;
 pub mod auction_module;
+pub mod sealed_bid_module;
+pub mod dispute_module;
+pub mod fixed_price_module;
+pub mod offer_module;
+pub mod governance_module;
 pub mod state;
 pub mod errors;
 
 use config_module::*;
 use asset_module::*;
 use auction_module::*;
+use sealed_bid_module::*;
+use dispute_module::*;
+use fixed_price_module::*;
+use offer_module::*;
+use governance_module::*;
 use state::*;
 
 declare_id!("3sfrzZ8nqttybB1egP7Sep1LxhHuPq7bmKg7v9TrYd9W");
 
-#[programs]
+#[program]
 pub mod dubuu_marketplace_mvp {
     use super::*;
     // Config Module Instructions
@@ -27,15 +37,61 @@ pub mod dubuu_marketplace_mvp {
         config_module::handle_initialize_config(ctx, admin, treasury_pern_account, listing_fee_usd_star, sale_commission_bps)
     }
 
+    pub fn migrate_config(
+        ctx: Context<MigrateConfigAccounts>,
+    ) -> Result<()> {
+        config_module::handle_migrate_config(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn update_config(
         ctx: Context<UpdateConfigAccounts>,
-        new_listing_fee: Option<u64>,
-        new_sale_commission: Option<u16>,
-        new_treasury: Option<Pubkey>,
         new_paused_status: Option<bool>,
-        new_admin: Option<Pubkey>,
+        new_anti_snipe_window_seconds: Option<i64>,
+        new_anti_snipe_extension_seconds: Option<i64>,
+        new_min_bid_increment_usd_star: Option<u64>,
+        new_min_bid_increment_bps: Option<u16>,
+        new_refund_listing_fee_on_cancel: Option<bool>,
+        new_max_auction_start_delay_seconds: Option<i64>,
+        new_min_auction_duration_seconds: Option<i64>,
+        new_max_auction_duration_seconds: Option<i64>,
+        new_sealed_bid_slash_bps: Option<u16>,
+        new_settlement_grace_period_seconds: Option<i64>,
+        new_delivery_confirmation_period_seconds: Option<i64>,
+        new_refund_listing_fee_on_admin_cancel: Option<bool>,
+    ) -> Result<()> {
+        config_module::handle_update_config(
+            ctx,
+            new_paused_status,
+            new_anti_snipe_window_seconds,
+            new_anti_snipe_extension_seconds,
+            new_min_bid_increment_usd_star,
+            new_min_bid_increment_bps,
+            new_refund_listing_fee_on_cancel,
+            new_max_auction_start_delay_seconds,
+            new_min_auction_duration_seconds,
+            new_max_auction_duration_seconds,
+            new_sealed_bid_slash_bps,
+            new_settlement_grace_period_seconds,
+            new_delivery_confirmation_period_seconds,
+            new_refund_listing_fee_on_admin_cancel,
+        )
+    }
+
+    pub fn grant_role(
+        ctx: Context<ManageRoleAccounts>,
+        role: MarketplaceRole,
+        holder: Pubkey,
+    ) -> Result<()> {
+        config_module::handle_grant_role(ctx, role, holder)
+    }
+
+    pub fn revoke_role(
+        ctx: Context<ManageRoleAccounts>,
+        role: MarketplaceRole,
+        holder: Pubkey,
     ) -> Result<()> {
-        config_module::handle_update_config(ctx, new_listing_fee, new_sale_commission, new_treasury, new_paused_status, new_admin)
+        config_module::handle_revoke_role(ctx, role, holder)
     }
 
     // Asset Module Instructions
@@ -62,6 +118,12 @@ pub mod dubuu_marketplace_mvp {
         asset_module::handle_update_asset_walrus_cid(ctx, new_walrus_main_metadata_cid)
     }
 
+    pub fn migrate_asset(
+        ctx: Context<MigrateAssetAccounts>,
+    ) -> Result<()> {
+        asset_module::handle_migrate_asset(ctx)
+    }
+
     // Auction Module Instructions
     pub fn initialize_wormhole_listener(
         ctx: Context<InitializeWormholeListenerAccounts>,
@@ -70,19 +132,95 @@ pub mod dubuu_marketplace_mvp {
         auction_module::handle_initialize_wormhole_listener(ctx, authorized_relayer)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn list_asset_for_auction(
         ctx: Context<ListAssetForAuctionAccounts>,
         start_price_usd_star: u64,
         duration_seconds: i64,
+        reserve_price_usd_star: Option<u64>,
+        is_reserve_price_hidden: bool,
+        min_bid_increment_usd_star: Option<u64>,
+        min_bid_increment_bps: Option<u16>,
+        buy_now_price_usd_star: Option<u64>,
+        start_timestamp: Option<i64>,
     ) -> Result<()> {
-        auction_module::handle_list_asset_for_auction(ctx, start_price_usd_star, duration_seconds)
+        auction_module::handle_list_asset_for_auction(
+            ctx,
+            start_price_usd_star,
+            duration_seconds,
+            reserve_price_usd_star,
+            is_reserve_price_hidden,
+            min_bid_increment_usd_star,
+            min_bid_increment_bps,
+            buy_now_price_usd_star,
+            start_timestamp,
+        )
+    }
+
+    pub fn list_asset_for_dutch_auction(
+        ctx: Context<ListAssetForAuctionAccounts>,
+        start_price_usd_star: u64,
+        floor_price_usd_star: u64,
+        duration_seconds: i64,
+        price_step_seconds: i64,
+        start_timestamp: Option<i64>,
+    ) -> Result<()> {
+        auction_module::handle_list_asset_for_dutch_auction(
+            ctx,
+            start_price_usd_star,
+            floor_price_usd_star,
+            duration_seconds,
+            price_step_seconds,
+            start_timestamp,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn list_asset_for_sealed_auction(
+        ctx: Context<ListAssetForAuctionAccounts>,
+        min_bid_usd_star: u64,
+        commit_duration_seconds: i64,
+        reveal_duration_seconds: i64,
+        deposit_usd_star: u64,
+        reserve_price_usd_star: Option<u64>,
+        start_timestamp: Option<i64>,
+        is_second_price: bool,
+    ) -> Result<()> {
+        auction_module::handle_list_asset_for_sealed_auction(
+            ctx,
+            min_bid_usd_star,
+            commit_duration_seconds,
+            reveal_duration_seconds,
+            deposit_usd_star,
+            reserve_price_usd_star,
+            start_timestamp,
+            is_second_price,
+        )
+    }
+
+    pub fn activate_auction(
+        ctx: Context<ActivateAuctionAccounts>,
+    ) -> Result<()> {
+        auction_module::handle_activate_auction(ctx)
     }
 
     pub fn place_bid(
         ctx: Context<PlaceBidAccounts>,
-        bid_amount_usd_star: u64,
+        max_bid_usd_star: u64,
     ) -> Result<()> {
-        auction_module::handle_place_bid(ctx, bid_amount_usd_star)
+        auction_module::handle_place_bid(ctx, max_bid_usd_star)
+    }
+
+    pub fn buy_dutch(
+        ctx: Context<BuyDutchAccounts>,
+    ) -> Result<()> {
+        auction_module::handle_buy_dutch(ctx)
+    }
+
+    pub fn withdraw_outbid_funds(
+        ctx: Context<WithdrawOutbidFundsAccounts>,
+    ) -> Result<()> {
+        auction_module::handle_withdraw_outbid_funds(ctx)
     }
 
     pub fn finalize_auction(
@@ -91,16 +229,188 @@ pub mod dubuu_marketplace_mvp {
         auction_module::handle_finalize_auction(ctx)
     }
 
+    pub fn cancel_auction(
+        ctx: Context<CancelAuctionAccounts>,
+    ) -> Result<()> {
+        auction_module::handle_cancel_auction(ctx)
+    }
+
+    pub fn collect_listing_fee(
+        ctx: Context<CollectListingFeeAccounts>,
+    ) -> Result<()> {
+        auction_module::handle_collect_listing_fee(ctx)
+    }
+
+    pub fn admin_cancel_auction(
+        ctx: Context<AdminCancelAuctionAccounts>,
+        flag_for_review: bool,
+    ) -> Result<()> {
+        auction_module::handle_admin_cancel_auction(ctx, flag_for_review)
+    }
+
     pub fn settle_auction_and_transfer(
         ctx: Context<SettleAuctionAccounts>,
     ) -> Result<()> {
         auction_module::handle_settle_auction_and_transfer(ctx)
     }
 
+    pub fn confirm_delivery(
+        ctx: Context<ConfirmDeliveryAccounts>,
+    ) -> Result<()> {
+        auction_module::handle_confirm_delivery(ctx)
+    }
+
+    pub fn crank_finalize_and_settle(
+        ctx: Context<CrankFinalizeAndSettleAccounts>,
+    ) -> Result<()> {
+        auction_module::handle_crank_finalize_and_settle(ctx)
+    }
+
     pub fn process_wormhole_balance_attestation(
         ctx: Context<ProcessWormholeAttestationAccounts>,
         payload: BalanceAttestationPayload,
     ) -> Result<()> {
         auction_module::handle_process_wormhole_balance_attestation(ctx, payload)
     }
+
+    // Sealed-Bid Module Instructions
+    pub fn commit_sealed_bid(
+        ctx: Context<CommitSealedBidAccounts>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        sealed_bid_module::handle_commit_sealed_bid(ctx, commitment)
+    }
+
+    pub fn reveal_sealed_bid(
+        ctx: Context<RevealSealedBidAccounts>,
+        amount_usd_star: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        sealed_bid_module::handle_reveal_sealed_bid(ctx, amount_usd_star, salt)
+    }
+
+    pub fn withdraw_sealed_bid(
+        ctx: Context<WithdrawSealedBidAccounts>,
+    ) -> Result<()> {
+        sealed_bid_module::handle_withdraw_sealed_bid(ctx)
+    }
+
+    // Dispute Module Instructions
+    pub fn open_dispute(
+        ctx: Context<OpenDisputeAccounts>,
+        evidence_cid: String,
+    ) -> Result<()> {
+        dispute_module::handle_open_dispute(ctx, evidence_cid)
+    }
+
+    pub fn submit_dispute_evidence(
+        ctx: Context<SubmitDisputeEvidenceAccounts>,
+        evidence_cid: String,
+    ) -> Result<()> {
+        dispute_module::handle_submit_dispute_evidence(ctx, evidence_cid)
+    }
+
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDisputeAccounts>,
+        seller_share_bps: u16,
+        ruling_cid: String,
+    ) -> Result<()> {
+        dispute_module::handle_resolve_dispute(ctx, seller_share_bps, ruling_cid)
+    }
+
+    // Fixed-Price Module Instructions
+    pub fn list_fixed_price(
+        ctx: Context<ListFixedPriceAccounts>,
+        price_usd_star: u64,
+    ) -> Result<()> {
+        fixed_price_module::handle_list_fixed_price(ctx, price_usd_star)
+    }
+
+    pub fn buy_fixed_price(
+        ctx: Context<BuyFixedPriceAccounts>,
+        expected_price_usd_star: u64,
+    ) -> Result<()> {
+        fixed_price_module::handle_buy_fixed_price(ctx, expected_price_usd_star)
+    }
+
+    pub fn update_price(
+        ctx: Context<UpdateFixedPriceAccounts>,
+        new_price_usd_star: u64,
+    ) -> Result<()> {
+        fixed_price_module::handle_update_price(ctx, new_price_usd_star)
+    }
+
+    pub fn delist(
+        ctx: Context<DelistFixedPriceAccounts>,
+    ) -> Result<()> {
+        fixed_price_module::handle_delist(ctx)
+    }
+
+    // Offer Module Instructions
+    pub fn make_offer(
+        ctx: Context<MakeOfferAccounts>,
+        amount_usd_star: u64,
+        expiry_timestamp: i64,
+    ) -> Result<()> {
+        offer_module::handle_make_offer(ctx, amount_usd_star, expiry_timestamp)
+    }
+
+    pub fn accept_offer(
+        ctx: Context<AcceptOfferAccounts>,
+    ) -> Result<()> {
+        offer_module::handle_accept_offer(ctx)
+    }
+
+    pub fn cancel_offer(
+        ctx: Context<CancelOfferAccounts>,
+    ) -> Result<()> {
+        offer_module::handle_cancel_offer(ctx)
+    }
+
+    // Governance Module Instructions
+    pub fn propose_config_change(
+        ctx: Context<ProposeConfigChangeAccounts>,
+        change: ConfigChange,
+        effective_at_timestamp: i64,
+        expiry_timestamp: i64,
+    ) -> Result<()> {
+        governance_module::handle_propose_config_change(ctx, change, effective_at_timestamp, expiry_timestamp)
+    }
+
+    pub fn approve_config_change(
+        ctx: Context<ApproveConfigChangeAccounts>,
+    ) -> Result<()> {
+        governance_module::handle_approve_config_change(ctx)
+    }
+
+    pub fn execute_config_change(
+        ctx: Context<ExecuteConfigChangeAccounts>,
+    ) -> Result<()> {
+        governance_module::handle_execute_config_change(ctx)
+    }
+
+    pub fn cancel_config_change(
+        ctx: Context<CancelConfigChangeAccounts>,
+    ) -> Result<()> {
+        governance_module::handle_cancel_config_change(ctx)
+    }
+
+    pub fn propose_admin(
+        ctx: Context<ProposeConfigChangeAccounts>,
+        proposed_admin: Pubkey,
+    ) -> Result<()> {
+        governance_module::handle_propose_admin(ctx, proposed_admin)
+    }
+
+    pub fn accept_admin(
+        ctx: Context<AcceptAdminAccounts>,
+    ) -> Result<()> {
+        governance_module::handle_accept_admin(ctx)
+    }
+
+    pub fn cancel_admin_transfer(
+        ctx: Context<CancelAdminTransferAccounts>,
+    ) -> Result<()> {
+        governance_module::handle_cancel_admin_transfer(ctx)
+    }
 }
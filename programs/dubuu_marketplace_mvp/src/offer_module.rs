@@ -0,0 +1,353 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Mint, Transfer, CloseAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::asset_module;
+use crate::auction_module;
+
+// Offers let a buyer bid on a verified asset outside of any listing. The offered amount sits
+// in a per-offer escrow until the owner accepts it, the buyer cancels it, or it expires and
+// anyone returns it to the buyer.
+
+#[derive(Accounts)]
+pub struct MakeOfferAccounts<'info> {
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 1 + 2,
+        seeds = [b"offer".as_ref(), asset_account.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub offer_account: Account<'info, OfferAccount>,
+
+    #[account(
+        constraint = asset_account.ownership_verification_status == OwnershipStatus::Verified @ DubuuMarketplaceError::OwnershipVerificationRequired,
+        constraint = asset_account.current_owner != buyer.key() @ DubuuMarketplaceError::CannotOfferOnOwnAsset
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump,
+        constraint = !marketplace_config.is_paused @ DubuuMarketplaceError::MarketplacePaused
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        mut,
+        constraint = buyer_pern_token_account.mint == marketplace_config.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = buyer_pern_token_account.owner == buyer.key() @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub buyer_pern_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = buyer,
+        token::mint = pern_usd_star_mint_account,
+        token::authority = offer_escrow_authority,
+        seeds = [b"offer_escrow".as_ref(), offer_account.key().as_ref()],
+        bump
+    )]
+    pub offer_escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        address = marketplace_config.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub pern_usd_star_mint_account: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA that will be the authority for the offer escrow account's tokens.
+    #[account(
+        seeds = [b"offer_escrow_authority".as_ref(), offer_account.key().as_ref()],
+        bump
+    )]
+    pub offer_escrow_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOfferAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"offer".as_ref(), offer_account.asset_key.as_ref(), offer_account.buyer.as_ref()],
+        bump = offer_account.bump,
+        close = buyer
+    )]
+    pub offer_account: Account<'info, OfferAccount>,
+
+    // Assets in an auction or a fixed-price listing must be delisted before an offer can be accepted.
+    #[account(
+        mut,
+        constraint = asset_account.key() == offer_account.asset_key @ DubuuMarketplaceError::InvalidAssetAccount,
+        constraint = asset_account.current_owner == seller.key() @ DubuuMarketplaceError::Unauthorized,
+        constraint = asset_account.ownership_verification_status == OwnershipStatus::Verified @ DubuuMarketplaceError::OwnershipVerificationRequired,
+        constraint = asset_account.asset_listed_status == AssetListedStatus::ReadyForAuction ||
+            asset_account.asset_listed_status == AssetListedStatus::Sold @ DubuuMarketplaceError::InvalidAssetStatus
+    )]
+    pub asset_account: Account<'info, AssetAccount>,
+
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_pern_token_account.mint == offer_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = seller_pern_token_account.owner == seller.key() @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub seller_pern_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Buyer who made the offer; receives the offer and escrow rent back.
+    #[account(mut, address = offer_account.buyer @ DubuuMarketplaceError::Unauthorized)]
+    pub buyer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offer_escrow".as_ref(), offer_account.key().as_ref()],
+        bump
+    )]
+    pub offer_escrow_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA authority for the offer escrow account's tokens.
+    #[account(
+        seeds = [b"offer_escrow_authority".as_ref(), offer_account.key().as_ref()],
+        bump = offer_account.escrow_authority_bump
+    )]
+    pub offer_escrow_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump,
+        constraint = !marketplace_config.is_paused @ DubuuMarketplaceError::MarketplacePaused
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
+        constraint = treasury_pern_token_account.mint == offer_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOfferAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"offer".as_ref(), offer_account.asset_key.as_ref(), offer_account.buyer.as_ref()],
+        bump = offer_account.bump,
+        close = buyer
+    )]
+    pub offer_account: Account<'info, OfferAccount>,
+
+    /// CHECK: Buyer who made the offer; receives the refund and rent. Anyone may crank an expired offer.
+    #[account(mut, address = offer_account.buyer @ DubuuMarketplaceError::Unauthorized)]
+    pub buyer: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = buyer_pern_token_account.mint == offer_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = buyer_pern_token_account.owner == offer_account.buyer @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub buyer_pern_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"offer_escrow".as_ref(), offer_account.key().as_ref()],
+        bump
+    )]
+    pub offer_escrow_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA authority for the offer escrow account's tokens.
+    #[account(
+        seeds = [b"offer_escrow_authority".as_ref(), offer_account.key().as_ref()],
+        bump = offer_account.escrow_authority_bump
+    )]
+    pub offer_escrow_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, token::Token>,
+}
+
+// --- Instruction Handlers ---
+
+pub fn handle_make_offer(
+    ctx: Context<MakeOfferAccounts>,
+    amount_usd_star: u64,
+    expiry_timestamp: i64,
+) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    require!(amount_usd_star > 0, DubuuMarketplaceError::InvalidOfferAmount);
+    require!(expiry_timestamp > current_timestamp, DubuuMarketplaceError::InvalidOfferExpiry);
+
+    let cpi_accounts_escrow = Transfer {
+        from: ctx.accounts.buyer_pern_token_account.to_account_info(),
+        to: ctx.accounts.offer_escrow_token_account.to_account_info(),
+        authority: ctx.accounts.buyer.to_account_info(),
+    };
+    let cpi_ctx_escrow = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts_escrow,
+    );
+    token::transfer(cpi_ctx_escrow, amount_usd_star)?;
+
+    let offer_account = &mut ctx.accounts.offer_account;
+    offer_account.asset_key = ctx.accounts.asset_account.key();
+    offer_account.buyer = ctx.accounts.buyer.key();
+    offer_account.pern_usd_star_mint = ctx.accounts.marketplace_config.pern_usd_star_mint;
+    offer_account.amount_usd_star = amount_usd_star;
+    offer_account.created_at_timestamp = current_timestamp;
+    offer_account.expiry_timestamp = expiry_timestamp;
+    offer_account.escrow_authority_bump = ctx.bumps.offer_escrow_authority;
+    offer_account.bump = ctx.bumps.offer_account;
+    offer_account.sale_commission_bps = ctx.accounts.marketplace_config.sale_commission_bps;
+
+    emit!(OfferMadeEvent {
+        offer_key: offer_account.key(),
+        asset_key: offer_account.asset_key,
+        buyer: offer_account.buyer,
+        amount: amount_usd_star,
+        expiry_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn handle_accept_offer(ctx: Context<AcceptOfferAccounts>) -> Result<()> {
+    let offer_account = &ctx.accounts.offer_account;
+    require!(
+        Clock::get()?.unix_timestamp < offer_account.expiry_timestamp,
+        DubuuMarketplaceError::OfferExpired
+    );
+
+    let amount = offer_account.amount_usd_star;
+    let commission = auction_module::internal_sale_commission(
+        amount,
+        offer_account.sale_commission_bps,
+    )?;
+    let amount_to_seller = amount
+        .checked_sub(commission)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    let offer_key_as_bytes = offer_account.key().to_bytes();
+    let escrow_authority_seeds_slices: &[&[u8]] = &[
+        b"offer_escrow_authority".as_ref(),
+        offer_key_as_bytes.as_ref(),
+        &[offer_account.escrow_authority_bump],
+    ];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    if amount_to_seller > 0 {
+        let cpi_accounts_to_seller = Transfer {
+            from: ctx.accounts.offer_escrow_token_account.to_account_info(),
+            to: ctx.accounts.seller_pern_token_account.to_account_info(),
+            authority: ctx.accounts.offer_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_to_seller = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_to_seller,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_to_seller, amount_to_seller)?;
+    }
+
+    if commission > 0 {
+        let cpi_accounts_to_treasury = Transfer {
+            from: ctx.accounts.offer_escrow_token_account.to_account_info(),
+            to: ctx.accounts.treasury_pern_token_account.to_account_info(),
+            authority: ctx.accounts.offer_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_to_treasury = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_to_treasury,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_to_treasury, commission)?;
+    }
+
+    let cpi_accounts_close_escrow = CloseAccount {
+        account: ctx.accounts.offer_escrow_token_account.to_account_info(),
+        destination: ctx.accounts.buyer.to_account_info(),
+        authority: ctx.accounts.offer_escrow_authority.to_account_info(),
+    };
+    let cpi_ctx_close_escrow = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts_close_escrow,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx_close_escrow)?;
+
+    asset_module::internal_transfer_ownership(
+        &mut ctx.accounts.asset_account,
+        offer_account.buyer
+    )?;
+
+    emit!(OfferAcceptedEvent {
+        offer_key: offer_account.key(),
+        asset_key: offer_account.asset_key,
+        seller: ctx.accounts.seller.key(),
+        buyer: offer_account.buyer,
+        amount,
+    });
+
+    Ok(())
+}
+
+pub fn handle_cancel_offer(ctx: Context<CancelOfferAccounts>) -> Result<()> {
+    let offer_account = &ctx.accounts.offer_account;
+    let expired = Clock::get()?.unix_timestamp >= offer_account.expiry_timestamp;
+    require!(
+        expired || ctx.accounts.signer.key() == offer_account.buyer,
+        DubuuMarketplaceError::OfferNotExpired
+    );
+
+    let refund = ctx.accounts.offer_escrow_token_account.amount;
+    let offer_key_as_bytes = offer_account.key().to_bytes();
+    let escrow_authority_seeds_slices: &[&[u8]] = &[
+        b"offer_escrow_authority".as_ref(),
+        offer_key_as_bytes.as_ref(),
+        &[offer_account.escrow_authority_bump],
+    ];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    if refund > 0 {
+        let cpi_accounts_refund = Transfer {
+            from: ctx.accounts.offer_escrow_token_account.to_account_info(),
+            to: ctx.accounts.buyer_pern_token_account.to_account_info(),
+            authority: ctx.accounts.offer_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx_refund = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_refund,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx_refund, refund)?;
+    }
+
+    let cpi_accounts_close_escrow = CloseAccount {
+        account: ctx.accounts.offer_escrow_token_account.to_account_info(),
+        destination: ctx.accounts.buyer.to_account_info(),
+        authority: ctx.accounts.offer_escrow_authority.to_account_info(),
+    };
+    let cpi_ctx_close_escrow = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts_close_escrow,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx_close_escrow)?;
+
+    emit!(OfferCancelledEvent {
+        offer_key: offer_account.key(),
+        asset_key: offer_account.asset_key,
+        buyer: offer_account.buyer,
+        refunded_amount: refund,
+        expired,
+    });
+
+    Ok(())
+}
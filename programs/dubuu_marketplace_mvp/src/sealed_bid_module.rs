@@ -0,0 +1,402 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash;
+use anchor_spl::token::{self, TokenAccount, Transfer, CloseAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::auction_module;
+
+// Sealed-bid auctions run in two phases on top of the regular AuctionAccount:
+// - commit (until auction_end_timestamp): bidders lock the auction's deposit in escrow
+//   together with hash(auction_key || bidder || amount_le_bytes || salt).
+// - reveal (until reveal_end_timestamp): bidders disclose amount and salt and top up
+//   the escrow to the full bid amount. finalize_auction then picks the highest reveal.
+// Every bidder reclaims whatever the auction does not keep through withdraw_sealed_bid.
+
+#[derive(Accounts)]
+pub struct CommitSealedBidAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_type == AuctionType::SealedBid @ DubuuMarketplaceError::InvalidAuctionType,
+        constraint = auction_account.auction_status == AuctionProcessStatus::Active ||
+            auction_account.auction_status == AuctionProcessStatus::PendingStart @ DubuuMarketplaceError::AuctionNotInActiveState
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + 32 + 32 + 32 + 8 + (1 + 8) + 1 + 8 + (4 + MAX_BID_HISTORY_ENTRIES * (8 + 8)),
+        seeds = [b"bid_record".as_ref(), auction_account.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_record: Account<'info, BidRecord>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bidder_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = bidder_pern_token_account.owner == bidder.key() @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub bidder_pern_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealSealedBidAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_type == AuctionType::SealedBid @ DubuuMarketplaceError::InvalidAuctionType,
+        constraint = auction_account.auction_status == AuctionProcessStatus::Active @ DubuuMarketplaceError::AuctionNotInActiveState
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"bid_record".as_ref(), auction_account.key().as_ref(), bidder.key().as_ref()],
+        bump = bid_record.bump,
+        has_one = bidder @ DubuuMarketplaceError::Unauthorized
+    )]
+    pub bid_record: Account<'info, BidRecord>,
+
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bidder_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = bidder_pern_token_account.owner == bidder.key() @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub bidder_pern_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, token::Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSealedBidAccounts<'info> {
+    #[account(
+        seeds = [b"auction".as_ref(), auction_account.asset_key.as_ref(), &auction_account.listing_nonce.to_le_bytes()],
+        bump = auction_account.bump,
+        constraint = auction_account.auction_type == AuctionType::SealedBid @ DubuuMarketplaceError::InvalidAuctionType
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"bid_record".as_ref(), auction_account.key().as_ref(), bid_record.bidder.as_ref()],
+        bump = bid_record.bump,
+        close = bidder
+    )]
+    pub bid_record: Account<'info, BidRecord>,
+
+    /// CHECK: Owner of the bid record; receives the record's rent. Anyone may crank the withdrawal.
+    #[account(mut, address = bid_record.bidder @ DubuuMarketplaceError::Unauthorized)]
+    pub bidder: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bidder_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint,
+        constraint = bidder_pern_token_account.owner == bid_record.bidder @ DubuuMarketplaceError::InvalidTokenAccountOwner
+    )]
+    pub bidder_pern_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"marketplace_config".as_ref()],
+        bump = marketplace_config.bump
+    )]
+    pub marketplace_config: Account<'info, MarketplaceConfig>,
+
+    #[account(
+        mut,
+        address = marketplace_config.treasury_pern_account @ DubuuMarketplaceError::InvalidTreasuryAccount,
+        constraint = treasury_pern_token_account.mint == auction_account.pern_usd_star_mint @ DubuuMarketplaceError::InvalidPerenaMint
+    )]
+    pub treasury_pern_token_account: Account<'info, TokenAccount>,
+
+    // Omitted once the escrow has been closed and the record holds nothing.
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), auction_account.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA authority for the escrow account's tokens.
+    #[account(
+        seeds = [b"escrow_authority".as_ref(), auction_account.key().as_ref()],
+        bump = auction_account.escrow_authority_bump
+    )]
+    pub auction_escrow_authority: AccountInfo<'info>,
+
+    /// CHECK: Seller's account to return the escrow rent to once the last deposit is withdrawn.
+    #[account(mut, address = auction_account.seller @ DubuuMarketplaceError::InvalidSellerAccountForRent)]
+    pub seller_rent_recipient: AccountInfo<'info>,
+
+    pub token_program: Program<'info, token::Token>,
+}
+
+// --- Instruction Handlers ---
+
+pub fn handle_commit_sealed_bid(
+    ctx: Context<CommitSealedBidAccounts>,
+    commitment: [u8; 32],
+) -> Result<()> {
+    let auction_account = &mut ctx.accounts.auction_account;
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= auction_account.auction_start_timestamp,
+        DubuuMarketplaceError::AuctionNotStarted
+    );
+    require!(
+        clock.unix_timestamp < auction_account.auction_end_timestamp,
+        DubuuMarketplaceError::CommitPhaseEnded
+    );
+    if auction_account.auction_status == AuctionProcessStatus::PendingStart {
+        auction_module::internal_activate_pending_auction(auction_account)?;
+    }
+
+    let deposit = auction_account.sealed_bid_deposit_usd_star;
+    let cpi_accounts_deposit = Transfer {
+        from: ctx.accounts.bidder_pern_token_account.to_account_info(),
+        to: ctx.accounts.auction_escrow_token_account.to_account_info(),
+        authority: ctx.accounts.bidder.to_account_info(),
+    };
+    let cpi_ctx_deposit = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts_deposit,
+    );
+    token::transfer(cpi_ctx_deposit, deposit)?;
+
+    let bid_record = &mut ctx.accounts.bid_record;
+    bid_record.auction_key = auction_account.key();
+    bid_record.bidder = ctx.accounts.bidder.key();
+    bid_record.commitment = commitment;
+    bid_record.escrowed_usd_star = deposit;
+    bid_record.revealed_bid_usd_star = None;
+    bid_record.bump = ctx.bumps.bid_record;
+
+    auction_account.bid_count = auction_account.bid_count
+        .checked_add(1)
+        .ok_or(DubuuMarketplaceError::CalculationOverflow)?;
+
+    emit!(SealedBidCommittedEvent {
+        auction_key: auction_account.key(),
+        bidder: ctx.accounts.bidder.key(),
+        deposit,
+    });
+
+    Ok(())
+}
+
+pub fn handle_reveal_sealed_bid(
+    ctx: Context<RevealSealedBidAccounts>,
+    amount_usd_star: u64,
+    salt: [u8; 32],
+) -> Result<()> {
+    let auction_account = &mut ctx.accounts.auction_account;
+    let bid_record = &mut ctx.accounts.bid_record;
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= auction_account.auction_end_timestamp &&
+        clock.unix_timestamp < auction_account.reveal_end_timestamp,
+        DubuuMarketplaceError::RevealPhaseNotActive
+    );
+    require!(
+        bid_record.revealed_bid_usd_star.is_none(),
+        DubuuMarketplaceError::BidAlreadyRevealed
+    );
+
+    let auction_key = auction_account.key();
+    let expected_commitment = hash::hashv(&[
+        auction_key.as_ref(),
+        bid_record.bidder.as_ref(),
+        &amount_usd_star.to_le_bytes(),
+        &salt,
+    ]).to_bytes();
+    require!(
+        expected_commitment == bid_record.commitment,
+        DubuuMarketplaceError::InvalidBidReveal
+    );
+
+    bid_record.revealed_bid_usd_star = Some(amount_usd_star);
+    auction_module::internal_record_bid(bid_record, amount_usd_star, clock.unix_timestamp);
+
+    // Bids under the minimum count as revealed (no slashing) but can never win.
+    // Ties go to whoever revealed first.
+    let is_highest = amount_usd_star >= auction_account.start_price_usd_star &&
+        (auction_account.highest_bidder.is_none() || amount_usd_star > auction_account.highest_bid_usd_star);
+
+    if is_highest {
+        if amount_usd_star > bid_record.escrowed_usd_star {
+            let top_up = amount_usd_star - bid_record.escrowed_usd_star;
+            let cpi_accounts_top_up = Transfer {
+                from: ctx.accounts.bidder_pern_token_account.to_account_info(),
+                to: ctx.accounts.auction_escrow_token_account.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+            };
+            let cpi_ctx_top_up = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts_top_up,
+            );
+            token::transfer(cpi_ctx_top_up, top_up)?;
+            bid_record.escrowed_usd_star = amount_usd_star;
+        }
+
+        // The displaced leader becomes the runner-up for second-price settlement.
+        if auction_account.highest_bidder.is_some() {
+            auction_account.second_highest_bid_usd_star = auction_account.highest_bid_usd_star;
+        }
+        auction_account.highest_bid_usd_star = amount_usd_star;
+        auction_account.highest_max_bid_usd_star = amount_usd_star;
+        auction_account.highest_bidder = Some(bid_record.bidder);
+    } else if amount_usd_star >= auction_account.start_price_usd_star &&
+        amount_usd_star > auction_account.second_highest_bid_usd_star
+    {
+        auction_account.second_highest_bid_usd_star = amount_usd_star;
+    }
+
+    emit!(SealedBidRevealedEvent {
+        auction_key,
+        bidder: bid_record.bidder,
+        amount: amount_usd_star,
+        is_highest,
+    });
+
+    Ok(())
+}
+
+pub fn handle_withdraw_sealed_bid(ctx: Context<WithdrawSealedBidAccounts>) -> Result<()> {
+    let auction_account = &ctx.accounts.auction_account;
+    let bid_record = &ctx.accounts.bid_record;
+
+    let is_winner = auction_account.highest_bidder == Some(bid_record.bidder);
+    let (refund, slashed) = match auction_account.auction_status {
+        AuctionProcessStatus::Cancelled => (bid_record.escrowed_usd_star, 0),
+        // Settlement pays out the winning bid (and any second-price refund), and a refunding dispute
+        // ruling returns it; only the excess deposit is left here.
+        AuctionProcessStatus::Completed |
+        AuctionProcessStatus::Refunded |
+        AuctionProcessStatus::AwaitingDelivery |
+        AuctionProcessStatus::Disputed if is_winner => (
+            bid_record.escrowed_usd_star
+                .checked_sub(auction_account.highest_bid_usd_star)
+                .ok_or(DubuuMarketplaceError::CalculationOverflow)?,
+            0,
+        ),
+        AuctionProcessStatus::EndedSoldPayPending if is_winner => {
+            return err!(DubuuMarketplaceError::WithdrawalNotAvailable);
+        }
+        AuctionProcessStatus::EndedUnsold |
+        AuctionProcessStatus::EndedReserveNotMet |
+        AuctionProcessStatus::EndedSoldPayPending |
+        AuctionProcessStatus::AwaitingDelivery |
+        AuctionProcessStatus::Disputed |
+        AuctionProcessStatus::Completed |
+        AuctionProcessStatus::Refunded => {
+            if bid_record.revealed_bid_usd_star.is_some() {
+                (bid_record.escrowed_usd_star, 0)
+            } else {
+                let slashed = (bid_record.escrowed_usd_star as u128)
+                    .checked_mul(auction_account.sealed_bid_slash_bps as u128)
+                    .ok_or(DubuuMarketplaceError::CalculationOverflow)?
+                    / 10000;
+                let slashed = slashed as u64;
+                (bid_record.escrowed_usd_star - slashed, slashed)
+            }
+        }
+        _ => return err!(DubuuMarketplaceError::WithdrawalNotAvailable),
+    };
+
+    let auction_key_as_bytes = auction_account.key().to_bytes();
+    let escrow_authority_seeds_slices: &[&[u8]] = &[
+        b"escrow_authority".as_ref(),
+        auction_key_as_bytes.as_ref(),
+        &[auction_account.escrow_authority_bump],
+    ];
+    let signer_seeds = &[escrow_authority_seeds_slices];
+
+    if let Some(auction_escrow_token_account) = ctx.accounts.auction_escrow_token_account.as_mut() {
+        if refund > 0 {
+            let cpi_accounts_refund = Transfer {
+                from: auction_escrow_token_account.to_account_info(),
+                to: ctx.accounts.bidder_pern_token_account.to_account_info(),
+                authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+            };
+            let cpi_ctx_refund = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts_refund,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx_refund, refund)?;
+        }
+
+        if slashed > 0 {
+            let cpi_accounts_slash = Transfer {
+                from: auction_escrow_token_account.to_account_info(),
+                to: ctx.accounts.treasury_pern_token_account.to_account_info(),
+                authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+            };
+            let cpi_ctx_slash = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts_slash,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx_slash, slashed)?;
+        }
+
+        // Close the escrow once the last deposit has left a settled or abandoned auction.
+        auction_escrow_token_account.reload()?;
+        if auction_escrow_token_account.amount == 0 &&
+            auction_account.auction_status != AuctionProcessStatus::EndedSoldPayPending &&
+            auction_account.auction_status != AuctionProcessStatus::AwaitingDelivery &&
+            auction_account.auction_status != AuctionProcessStatus::Disputed
+        {
+            let cpi_accounts_close_escrow = CloseAccount {
+                account: auction_escrow_token_account.to_account_info(),
+                destination: ctx.accounts.seller_rent_recipient.to_account_info(),
+                authority: ctx.accounts.auction_escrow_authority.to_account_info(),
+            };
+            let cpi_ctx_close_escrow = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts_close_escrow,
+                signer_seeds,
+            );
+            token::close_account(cpi_ctx_close_escrow)?;
+        }
+    } else {
+        require!(refund == 0 && slashed == 0, DubuuMarketplaceError::MissingEscrowAccount);
+    }
+
+    emit!(SealedBidWithdrawnEvent {
+        auction_key: auction_account.key(),
+        bidder: bid_record.bidder,
+        refunded: refund,
+        slashed,
+    });
+
+    Ok(())
+}
@@ -11,6 +11,84 @@ pub struct MarketplaceConfig {
     pub sale_commission_bps: u16,
     pub is_paused: bool,
     pub bump: u8,
+    pub anti_snipe_window_seconds: i64,
+    pub anti_snipe_extension_seconds: i64,
+    pub min_bid_increment_usd_star: u64,
+    pub min_bid_increment_bps: u16,
+    // When false, sellers who cancel an auction forfeit the listing fee.
+    pub refund_listing_fee_on_cancel: bool,
+    pub max_auction_start_delay_seconds: i64,
+    pub min_auction_duration_seconds: i64,
+    pub max_auction_duration_seconds: i64,
+    pub sealed_bid_slash_bps: u16,
+    pub settlement_grace_period_seconds: i64,
+    // Share of the sale commission paid to whoever runs crank_finalize_and_settle.
+    pub keeper_fee_bps: u16,
+    // 0 disables the delivery stage; otherwise buyers have this long to confirm delivery before funds auto-release.
+    pub delivery_confirmation_period_seconds: i64,
+    // Set by an executed admin proposal; takes over only once it signs accept_admin.
+    pub pending_admin: Option<Pubkey>,
+    // Keys whose approvals count towards config_approval_threshold on sensitive config proposals.
+    pub config_signers: Vec<Pubkey>,
+    pub config_approval_threshold: u8,
+    // Nonce for the next ConfigProposal PDA.
+    pub config_proposal_count: u64,
+    // Minimum time between proposing a config change and it taking effect.
+    pub config_change_delay_seconds: i64,
+    // When false, the listing fee of an auction unwound by admin_cancel_auction goes to the treasury.
+    pub refund_listing_fee_on_admin_cancel: bool,
+}
+
+pub const MAX_ROLE_ASSIGNMENTS: usize = 20;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MarketplaceRole {
+    Verifier,
+    FeeManager,
+    Pauser,
+    TreasuryManager,
+    Arbiter,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RoleAssignment {
+    pub role: MarketplaceRole,
+    pub holder: Pubkey,
+}
+
+// Singleton PDA holding the operational roles split out of the admin key. A role may have several holders.
+#[account]
+#[derive(Debug)]
+pub struct RoleRegistry {
+    pub assignments: Vec<RoleAssignment>,
+    pub bump: u8,
+}
+
+// Governance Module State
+pub const MAX_CONFIG_SIGNERS: usize = 5;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ConfigChange {
+    ListingFee { listing_fee_usd_star: u64 },
+    KeeperFee { keeper_fee_bps: u16 },
+    SaleCommission { sale_commission_bps: u16 },
+    Treasury { treasury_pern_account: Pubkey },
+    Admin { proposed_admin: Pubkey },
+    ConfigSigners { signers: Vec<Pubkey>, threshold: u8 },
+    ChangeDelay { config_change_delay_seconds: i64 },
+}
+
+#[account]
+#[derive(Debug)]
+pub struct ConfigProposal {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub change: ConfigChange,
+    pub approvals: Vec<Pubkey>,
+    pub created_at_timestamp: i64,
+    pub expiry_timestamp: i64,
+    pub bump: u8,
+    pub effective_at_timestamp: i64,
 }
 
 // Asset Module State
@@ -25,6 +103,8 @@ pub struct AssetAccount {
     pub asset_listed_status: AssetListedStatus,
     pub active_auction_key: Option<Pubkey>,
     pub bump: u8,
+    // Number of auctions ever opened for this asset; seeds the next AuctionAccount PDA.
+    pub auction_count: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
@@ -42,6 +122,7 @@ pub enum AssetListedStatus {
     ReadyForAuction,
     InAuction,
     Sold,
+    ListedFixedPrice,
 }
 
 // Auction Module State
@@ -58,6 +139,66 @@ pub struct AuctionAccount {
     pub auction_status: AuctionProcessStatus,
     pub escrow_authority_bump: u8,
     pub bump: u8,
+    pub reserve_price_usd_star: Option<u64>,
+    // Reserve values are readable on-chain; this only tells clients not to display it before the auction ends.
+    pub is_reserve_price_hidden: bool,
+    pub anti_snipe_window_seconds: i64,
+    pub anti_snipe_extension_seconds: i64,
+    pub min_bid_increment_usd_star: u64,
+    pub min_bid_increment_bps: u16,
+    pub buy_now_price_usd_star: Option<u64>,
+    pub listing_nonce: u64,
+    pub listing_fee_paid_usd_star: u64,
+    pub auction_start_timestamp: i64,
+    pub auction_type: AuctionType,
+    pub dutch_floor_price_usd_star: u64,
+    // 0 means the Dutch price decays linearly every second.
+    pub dutch_price_step_seconds: i64,
+    pub bid_count: u32,
+    // Sealed-bid auctions: commits are accepted until auction_end_timestamp, reveals until reveal_end_timestamp.
+    pub reveal_end_timestamp: i64,
+    pub sealed_bid_deposit_usd_star: u64,
+    pub sealed_bid_slash_bps: u16,
+    // Second-price (Vickrey) sealed auctions settle at max(second highest bid, reserve, minimum bid).
+    pub is_second_price: bool,
+    pub second_highest_bid_usd_star: u64,
+    // Amount the winner actually pays; set when the auction is won.
+    pub clearing_price_usd_star: u64,
+    // Maximum the current leader has escrowed; highest_bid_usd_star is the visible price.
+    pub highest_max_bid_usd_star: u64,
+    // Once the auction is won, only the winner may settle until settlement_deadline_timestamp.
+    pub settlement_grace_period_seconds: i64,
+    pub settlement_deadline_timestamp: i64,
+    // When non-zero, settlement holds the proceeds in escrow until confirm_delivery or delivery_deadline_timestamp.
+    pub delivery_confirmation_period_seconds: i64,
+    pub delivery_deadline_timestamp: i64,
+    // Commission and keeper fee in force at listing; settlement uses these rather than the live config.
+    pub sale_commission_bps: u16,
+    pub keeper_fee_bps: u16,
+}
+
+#[account]
+#[derive(Debug)]
+pub struct BidRecord {
+    pub auction_key: Pubkey,
+    pub bidder: Pubkey,
+    pub commitment: [u8; 32],
+    // Total funds this bidder currently holds in the auction escrow.
+    pub escrowed_usd_star: u64,
+    pub revealed_bid_usd_star: Option<u64>,
+    pub bump: u8,
+    // English auctions: the most this bidder is willing to pay through proxy bidding.
+    pub max_bid_usd_star: u64,
+    // Rolling log of this bidder's bids; the oldest entry is dropped past MAX_BID_HISTORY_ENTRIES.
+    pub bid_history: Vec<BidHistoryEntry>,
+}
+
+pub const MAX_BID_HISTORY_ENTRIES: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BidHistoryEntry {
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
 #[account]
@@ -78,6 +219,13 @@ pub struct WormholeListenerConfig {
     pub bump: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum AuctionType {
+    English,
+    Dutch,
+    SealedBid,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
 pub enum AuctionProcessStatus {
     PendingStart,
@@ -85,9 +233,149 @@ pub enum AuctionProcessStatus {
     EndedUnsold,
     EndedSoldPayPending,
     Completed,
+    EndedReserveNotMet,
+    Cancelled,
+    AwaitingDelivery,
+    Disputed,
+    // A dispute ruled fully for the buyer: the sale was unwound and the winner refunded.
+    Refunded,
+}
+
+// Fixed-Price Module State
+#[account]
+#[derive(Debug)]
+pub struct FixedPriceListing {
+    pub asset_key: Pubkey,
+    pub seller: Pubkey,
+    pub pern_usd_star_mint: Pubkey,
+    pub price_usd_star: u64,
+    pub listing_fee_paid_usd_star: u64,
+    pub listed_at_timestamp: i64,
+    pub bump: u8,
+    // Commission in force at listing; the sale uses it rather than the live config.
+    pub sale_commission_bps: u16,
+}
+
+// Offer Module State
+#[account]
+#[derive(Debug)]
+pub struct OfferAccount {
+    pub asset_key: Pubkey,
+    pub buyer: Pubkey,
+    pub pern_usd_star_mint: Pubkey,
+    pub amount_usd_star: u64,
+    pub created_at_timestamp: i64,
+    pub expiry_timestamp: i64,
+    pub escrow_authority_bump: u8,
+    pub bump: u8,
+    // Commission in force when the offer was made; acceptance uses it rather than the live config.
+    pub sale_commission_bps: u16,
+}
+
+// Dispute Module State
+#[account]
+#[derive(Debug)]
+pub struct DisputeAccount {
+    pub auction_key: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub opened_at_timestamp: i64,
+    pub buyer_evidence_cid: String,
+    pub seller_evidence_cid: String,
+    pub ruling_cid: String,
+    pub status: DisputeStatus,
+    // Share of the sale amount released to the seller (the rest is refunded to the buyer).
+    pub seller_share_bps: u16,
+    pub resolved_at_timestamp: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum DisputeStatus {
+    Open,
+    Resolved,
 }
 
 // Event Structs
+#[event]
+#[derive(Debug)]
+pub struct AdminTransferProposedEvent {
+    pub admin: Pubkey,
+    pub proposed_admin: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AdminTransferAcceptedEvent {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AdminTransferCancelledEvent {
+    pub admin: Pubkey,
+    pub cancelled_admin: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ConfigChangeProposedEvent {
+    pub proposal_key: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub change: ConfigChange,
+    pub effective_at_timestamp: i64,
+    pub expiry_timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ConfigChangeApprovedEvent {
+    pub proposal_key: Pubkey,
+    pub signer: Pubkey,
+    pub approval_count: u8,
+}
+
+// Emitted once a proposal has enough approvals, so clients can warn users ahead of effective_at.
+#[event]
+#[derive(Debug)]
+pub struct ConfigChangeQueuedEvent {
+    pub proposal_key: Pubkey,
+    pub change: ConfigChange,
+    pub effective_at_timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ConfigChangeExecutedEvent {
+    pub proposal_key: Pubkey,
+    pub change: ConfigChange,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ConfigChangeCancelledEvent {
+    pub proposal_key: Pubkey,
+    pub expired: bool,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RoleGrantedEvent {
+    pub role: MarketplaceRole,
+    pub holder: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RoleRevokedEvent {
+    pub role: MarketplaceRole,
+    pub holder: Pubkey,
+    pub admin: Pubkey,
+}
+
 #[event]
 #[derive(Debug)]
 pub struct OwnershipVerificationUpdatedEvent {
@@ -104,9 +392,56 @@ pub struct AssetSold {
     pub walrus_main_metadata_cid: String,
 }
 
+#[event]
+#[derive(Debug)]
+pub struct AuctionStartedEvent {
+    pub auction_key: Pubkey,
+    pub auction_start_timestamp: i64,
+    pub auction_end_timestamp: i64,
+}
+
 #[event]
 #[derive(Debug)]
 pub struct BidPlacedEvent {
+    pub auction_key: Pubkey,
+    pub bidder: Pubkey,
+    // Leader after the bid; differs from `bidder` when a proxy answered the challenge.
+    pub highest_bidder: Pubkey,
+    pub amount: u64,
+    pub auction_end_timestamp: i64,
+    // Set when the standing leader's maximum answered a lower challenger automatically.
+    pub is_proxy_bid: bool,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct SealedBidCommittedEvent {
+    pub auction_key: Pubkey,
+    pub bidder: Pubkey,
+    pub deposit: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct SealedBidRevealedEvent {
+    pub auction_key: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub is_highest: bool,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct SealedBidWithdrawnEvent {
+    pub auction_key: Pubkey,
+    pub bidder: Pubkey,
+    pub refunded: u64,
+    pub slashed: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct OutbidFundsWithdrawnEvent {
     pub auction_key: Pubkey,
     pub bidder: Pubkey,
     pub amount: u64,
@@ -118,6 +453,124 @@ pub struct AuctionEndedWinner {
     pub auction_key: Pubkey,
     pub winner: Pubkey,
     pub winning_bid: u64,
+    pub clearing_price: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct KeeperFeePaidEvent {
+    pub auction_key: Pubkey,
+    pub keeper: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AwaitingDeliveryEvent {
+    pub auction_key: Pubkey,
+    pub buyer: Pubkey,
+    pub delivery_deadline_timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DeliveryConfirmedEvent {
+    pub auction_key: Pubkey,
+    pub buyer: Pubkey,
+    // True when the delivery window lapsed and the crank released the funds.
+    pub auto_released: bool,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DisputeOpenedEvent {
+    pub dispute_key: Pubkey,
+    pub auction_key: Pubkey,
+    pub buyer: Pubkey,
+    pub evidence_cid: String,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DisputeEvidenceSubmittedEvent {
+    pub dispute_key: Pubkey,
+    pub submitter: Pubkey,
+    pub evidence_cid: String,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DisputeResolvedEvent {
+    pub dispute_key: Pubkey,
+    pub auction_key: Pubkey,
+    pub arbiter: Pubkey,
+    pub seller_share_bps: u16,
+    pub amount_to_seller: u64,
+    pub buyer_refund: u64,
+    pub ruling_cid: String,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct FixedPriceListedEvent {
+    pub listing_key: Pubkey,
+    pub asset_key: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct FixedPriceUpdatedEvent {
+    pub listing_key: Pubkey,
+    pub old_price: u64,
+    pub new_price: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct FixedPriceSoldEvent {
+    pub listing_key: Pubkey,
+    pub asset_key: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct FixedPriceDelistedEvent {
+    pub listing_key: Pubkey,
+    pub asset_key: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct OfferMadeEvent {
+    pub offer_key: Pubkey,
+    pub asset_key: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub expiry_timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct OfferAcceptedEvent {
+    pub offer_key: Pubkey,
+    pub asset_key: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct OfferCancelledEvent {
+    pub offer_key: Pubkey,
+    pub asset_key: Pubkey,
+    pub buyer: Pubkey,
+    pub refunded_amount: u64,
+    pub expired: bool,
 }
 
 #[event]
@@ -126,6 +579,57 @@ pub struct AuctionEndedNoSale {
     pub auction_key: Pubkey,
 }
 
+#[event]
+#[derive(Debug)]
+pub struct AuctionBoughtNowEvent {
+    pub auction_key: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DutchAuctionPurchasedEvent {
+    pub auction_key: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AuctionEndedReserveNotMet {
+    pub auction_key: Pubkey,
+    pub highest_bidder: Pubkey,
+    pub highest_bid: u64,
+    pub reserve_price: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AuctionCancelledBySellerEvent {
+    pub auction_key: Pubkey,
+    pub seller: Pubkey,
+    pub listing_fee_refunded: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ListingFeeCollectedEvent {
+    pub auction_key: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AuctionCancelledByAdminEvent {
+    pub auction_key: Pubkey,
+    pub authority: Pubkey,
+    // Bidders, including this one, reclaim their escrow through the withdraw instructions.
+    pub highest_bidder: Option<Pubkey>,
+    pub flagged_for_review: bool,
+    pub listing_fee_refunded: u64,
+}
+
 #[event]
 #[derive(Debug)]
 pub struct CrossChainBalanceAttestedEvent {
@@ -0,0 +1,139 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  LISTING_FEE,
+  MIN_AUCTION_DURATION,
+  Marketplace,
+  User,
+  accountExists,
+  bidRecordPda,
+  configPda,
+  createUser,
+  createVerifiedAsset,
+  disputePda,
+  escrowAuthorityPda,
+  escrowPda,
+  expectError,
+  finalizeAuction,
+  listEnglishAuction,
+  placeBid,
+  roleRegistryPda,
+  settleAuction,
+  setupMarketplace,
+  tokenBalance,
+  updateConfig,
+  warpBy,
+  withdrawOutbidFunds,
+} from "./helpers";
+
+describe("disputes", () => {
+  let marketplace: Marketplace;
+  let seller: User;
+  let buyer: User;
+  let asset: PublicKey;
+  let auction: PublicKey;
+
+  // Every test starts from a sold auction whose proceeds are held for delivery confirmation.
+  beforeEach(async () => {
+    marketplace = await setupMarketplace();
+    await updateConfig(marketplace, { deliveryConfirmationPeriodSeconds: 600 });
+    seller = await createUser(marketplace, 10_000);
+    buyer = await createUser(marketplace, 10_000);
+    asset = await createVerifiedAsset(marketplace, seller);
+    auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 2_000 });
+    await placeBid(marketplace, auction, buyer, 2_000);
+    await warpBy(marketplace, MIN_AUCTION_DURATION + 1);
+    await finalizeAuction(marketplace, auction);
+    await settleAuction(marketplace, auction, seller, buyer);
+  });
+
+  async function openDispute() {
+    await marketplace.program.methods
+      .openDispute("bafy-buyer-evidence")
+      .accountsPartial({
+        auctionAccount: auction,
+        disputeAccount: disputePda(marketplace.program, auction),
+        buyer: buyer.publicKey,
+      })
+      .signers([buyer.keypair])
+      .rpc();
+  }
+
+  async function submitEvidence(submitter: Keypair, evidenceCid: string) {
+    await marketplace.program.methods
+      .submitDisputeEvidence(evidenceCid)
+      .accountsPartial({
+        disputeAccount: disputePda(marketplace.program, auction),
+        submitter: submitter.publicKey,
+      })
+      .signers([submitter])
+      .rpc();
+  }
+
+  async function resolveDispute(arbiter: Keypair, sellerShareBps: number) {
+    await marketplace.program.methods
+      .resolveDispute(sellerShareBps, "bafy-ruling")
+      .accountsPartial({
+        disputeAccount: disputePda(marketplace.program, auction),
+        auctionAccount: auction,
+        marketplaceConfig: configPda(marketplace.program),
+        roleRegistry: roleRegistryPda(marketplace.program),
+        arbiter: arbiter.publicKey,
+        assetAccount: asset,
+        auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+        auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+        sellerTokenAccount: seller.tokenAccount,
+        buyer: buyer.publicKey,
+        buyerTokenAccount: buyer.tokenAccount,
+        treasuryPernTokenAccount: marketplace.treasury,
+      })
+      .signers([arbiter])
+      .rpc();
+  }
+
+  it("collects evidence from both parties and splits the escrow on resolution", async () => {
+    await openDispute();
+    let auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ disputed: {} });
+
+    await submitEvidence(seller.keypair, "bafy-seller-evidence");
+    await expectError(submitEvidence(Keypair.generate(), "bafy-outsider"), "Unauthorized");
+    const disputeAccount = await marketplace.program.account.disputeAccount.fetch(disputePda(marketplace.program, auction));
+    expect(disputeAccount.sellerEvidenceCid).to.equal("bafy-seller-evidence");
+
+    // Only the Arbiter role may rule; the admin holds it after initialization.
+    await expectError(resolveDispute(buyer.keypair, 5_000), "Unauthorized");
+    await resolveDispute(marketplace.admin, 5_000);
+
+    // Half the 2,000 sale goes to the seller less commission on that half; the buyer gets the rest back.
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 - LISTING_FEE + 950);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(50);
+    expect(await tokenBalance(marketplace, buyer.tokenAccount)).to.equal(9_000);
+
+    auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ completed: {} });
+    const assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.currentOwner.toBase58()).to.equal(buyer.publicKey.toBase58());
+  });
+
+  it("refunds the buyer in full and returns the asset to the seller", async () => {
+    await openDispute();
+    await resolveDispute(marketplace.admin, 0);
+
+    expect(await tokenBalance(marketplace, buyer.tokenAccount)).to.equal(10_000);
+    const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ refunded: {} });
+    const assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.currentOwner.toBase58()).to.equal(seller.publicKey.toBase58());
+    expect(assetAccount.assetListedStatus).to.deep.equal({ readyForAuction: {} });
+
+    // The refunded winner's record closes with nothing left to pay out.
+    await withdrawOutbidFunds(marketplace, auction, buyer, false);
+    expect(await accountExists(marketplace, bidRecordPda(marketplace.program, auction, buyer.publicKey))).to.be.false;
+  });
+
+  it("closes the dispute window with the delivery window", async () => {
+    await warpBy(marketplace, 601);
+    await expectError(openDispute(), "DisputeWindowClosed");
+  });
+});
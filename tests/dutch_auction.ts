@@ -0,0 +1,88 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  LISTING_FEE,
+  Marketplace,
+  User,
+  bidRecordPda,
+  createUser,
+  createVerifiedAsset,
+  escrowPda,
+  expectError,
+  listingAccounts,
+  setupMarketplace,
+  settleAuction,
+  tokenBalance,
+  warpTo,
+} from "./helpers";
+
+describe("dutch auctions", () => {
+  let marketplace: Marketplace;
+  let seller: User;
+  let buyer: User;
+  let asset: PublicKey;
+
+  beforeEach(async () => {
+    marketplace = await setupMarketplace();
+    seller = await createUser(marketplace, 10_000);
+    buyer = await createUser(marketplace, 20_000);
+    asset = await createVerifiedAsset(marketplace, seller);
+  });
+
+  async function listDutchAuction(startPrice: number, floorPrice: number, durationSeconds: number, stepSeconds: number) {
+    const { auction, accounts } = await listingAccounts(marketplace, seller, asset);
+    await marketplace.program.methods
+      .listAssetForDutchAuction(new BN(startPrice), new BN(floorPrice), new BN(durationSeconds), new BN(stepSeconds), null)
+      .accountsPartial(accounts)
+      .signers([seller.keypair])
+      .rpc();
+    return auction;
+  }
+
+  async function buyDutch(auction: PublicKey, who: User) {
+    await marketplace.program.methods
+      .buyDutch()
+      .accountsPartial({
+        auctionAccount: auction,
+        buyer: who.publicKey,
+        buyerPernTokenAccount: who.tokenAccount,
+        auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+        bidRecord: bidRecordPda(marketplace.program, auction, who.publicKey),
+      })
+      .signers([who.keypair])
+      .rpc();
+  }
+
+  it("rejects a floor at or above the start price", async () => {
+    await expectError(listDutchAuction(5_000, 5_000, 100, 0), "InvalidDutchPriceRange");
+  });
+
+  it("sells at the decayed price and settles like any won auction", async () => {
+    const auction = await listDutchAuction(10_000, 2_000, 100, 0);
+    const { auctionStartTimestamp } = await marketplace.program.account.auctionAccount.fetch(auction);
+
+    await warpTo(marketplace, auctionStartTimestamp.toNumber() + 50);
+    await buyDutch(auction, buyer);
+
+    const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.clearingPriceUsdStar.toNumber()).to.equal(6_000);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ endedSoldPayPending: {} });
+    expect(await tokenBalance(marketplace, buyer.tokenAccount)).to.equal(14_000);
+
+    await settleAuction(marketplace, auction, seller, buyer);
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 - LISTING_FEE + 5_700);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(300);
+  });
+
+  it("drops the price in whole steps", async () => {
+    const auction = await listDutchAuction(10_000, 2_000, 100, 25);
+    const { auctionStartTimestamp } = await marketplace.program.account.auctionAccount.fetch(auction);
+
+    await warpTo(marketplace, auctionStartTimestamp.toNumber() + 49);
+    await buyDutch(auction, buyer);
+
+    const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.clearingPriceUsdStar.toNumber()).to.equal(8_000);
+  });
+});
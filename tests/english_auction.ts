@@ -0,0 +1,411 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  LISTING_FEE,
+  MIN_AUCTION_DURATION,
+  Marketplace,
+  User,
+  accountExists,
+  bidRecordPda,
+  configPda,
+  createUser,
+  createVerifiedAsset,
+  escrowAuthorityPda,
+  escrowPda,
+  expectError,
+  finalizeAuction,
+  findEvent,
+  listEnglishAuction,
+  listingFeeVaultPda,
+  now,
+  placeBid,
+  placeBidMethod,
+  roleRegistryPda,
+  sendAndCollectEvents,
+  settleAuction,
+  setupMarketplace,
+  tokenBalance,
+  updateConfig,
+  warpBy,
+  warpTo,
+  withdrawOutbidFunds,
+} from "./helpers";
+
+describe("english auctions", () => {
+  let marketplace: Marketplace;
+  let seller: User;
+  let alice: User;
+  let bob: User;
+  let asset: PublicKey;
+
+  beforeEach(async () => {
+    marketplace = await setupMarketplace();
+    seller = await createUser(marketplace, 10_000);
+    alice = await createUser(marketplace, 10_000);
+    bob = await createUser(marketplace, 10_000);
+    asset = await createVerifiedAsset(marketplace, seller);
+  });
+
+  async function collectListingFee(auction: PublicKey) {
+    await marketplace.program.methods
+      .collectListingFee()
+      .accountsPartial({
+        auctionAccount: auction,
+        listingFeeVault: listingFeeVaultPda(marketplace.program, auction),
+        auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+        marketplaceConfig: configPda(marketplace.program),
+        treasuryPernTokenAccount: marketplace.treasury,
+        sellerRentRecipient: seller.publicKey,
+        signer: marketplace.provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+
+  async function cancelAuction(auction: PublicKey) {
+    await marketplace.program.methods
+      .cancelAuction()
+      .accountsPartial({
+        auctionAccount: auction,
+        seller: seller.publicKey,
+        assetAccount: asset,
+        marketplaceConfig: configPda(marketplace.program),
+        auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+        auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+        listingFeeVault: listingFeeVaultPda(marketplace.program, auction),
+        sellerPernTokenAccount: seller.tokenAccount,
+        treasuryPernTokenAccount: marketplace.treasury,
+      })
+      .signers([seller.keypair])
+      .rpc();
+  }
+
+  async function adminCancel(auction: PublicKey, signer: Keypair) {
+    await marketplace.program.methods
+      .adminCancelAuction(true)
+      .accountsPartial({
+        auctionAccount: auction,
+        marketplaceConfig: configPda(marketplace.program),
+        roleRegistry: roleRegistryPda(marketplace.program),
+        authority: signer.publicKey,
+        assetAccount: asset,
+        auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+        auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+        listingFeeVault: listingFeeVaultPda(marketplace.program, auction),
+        sellerPernTokenAccount: seller.tokenAccount,
+        treasuryPernTokenAccount: marketplace.treasury,
+        sellerRentRecipient: seller.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function crank(auction: PublicKey, keeper: User, winner: User | null) {
+    await marketplace.program.methods
+      .crankFinalizeAndSettle()
+      .accountsPartial({
+        auctionAccount: auction,
+        keeper: keeper.publicKey,
+        keeperTokenAccount: keeper.tokenAccount,
+        assetAccount: asset,
+        marketplaceConfig: configPda(marketplace.program),
+        auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+        auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+        sellerRentRecipient: seller.publicKey,
+        sellerTokenAccount: seller.tokenAccount,
+        treasuryPernTokenAccount: marketplace.treasury,
+        highestBidder: winner ? winner.publicKey : null,
+        highestBidderTokenAccount: winner ? winner.tokenAccount : null,
+      })
+      .signers([keeper.keypair])
+      .rpc();
+  }
+
+  it("holds the listing fee in a vault and sweeps it to the treasury once earned", async () => {
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000, reservePrice: 5_000 });
+    expect(await tokenBalance(marketplace, listingFeeVaultPda(marketplace.program, auction))).to.equal(LISTING_FEE);
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 - LISTING_FEE);
+
+    // Live auctions have not earned the fee yet: the seller or admin_cancel_auction may still unwind them.
+    await expectError(collectListingFee(auction), "ListingFeeNotCollectable");
+    await placeBid(marketplace, auction, alice, 1_000);
+    await expectError(collectListingFee(auction), "ListingFeeNotCollectable");
+
+    await warpBy(marketplace, MIN_AUCTION_DURATION + 1);
+    await finalizeAuction(marketplace, auction);
+    await collectListingFee(auction);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(LISTING_FEE);
+    expect(await accountExists(marketplace, listingFeeVaultPda(marketplace.program, auction))).to.be.false;
+  });
+
+  it("lets the seller cancel without the treasury signing, refunding the fee when configured", async () => {
+    await updateConfig(marketplace, { refundListingFeeOnCancel: true });
+    const refunded = await listEnglishAuction(marketplace, seller, asset);
+    await cancelAuction(refunded);
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000);
+    expect(await accountExists(marketplace, refunded)).to.be.false;
+
+    await updateConfig(marketplace, { refundListingFeeOnCancel: false });
+    const kept = await listEnglishAuction(marketplace, seller, asset);
+    await cancelAuction(kept);
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 - LISTING_FEE);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(LISTING_FEE);
+  });
+
+  it("refuses to cancel an auction that has bids", async () => {
+    const auction = await listEnglishAuction(marketplace, seller, asset);
+    await placeBid(marketplace, auction, alice, 1_000);
+    await expectError(cancelAuction(auction), "AuctionHasBids");
+  });
+
+  it("runs proxy bidding, outbid withdrawals and winner settlement", async () => {
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000 });
+
+    // The opening bid may match the start price; a higher maximum is charged the start price.
+    await placeBid(marketplace, auction, alice, 2_000);
+    let auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.highestBidUsdStar.toNumber()).to.equal(1_000);
+    expect(auctionAccount.highestBidder.toBase58()).to.equal(alice.publicKey.toBase58());
+
+    // A lower challenger is answered by the leader's proxy one unit above their maximum.
+    await placeBid(marketplace, auction, bob, 1_500);
+    auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.highestBidUsdStar.toNumber()).to.equal(1_501);
+    expect(auctionAccount.highestBidder.toBase58()).to.equal(alice.publicKey.toBase58());
+
+    // Outbidding the leader's maximum takes the lead at one unit over it.
+    await placeBid(marketplace, auction, bob, 2_500);
+    auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.highestBidUsdStar.toNumber()).to.equal(2_001);
+    expect(auctionAccount.highestBidder.toBase58()).to.equal(bob.publicKey.toBase58());
+
+    // The outbid bidder reclaims their escrow mid-auction; the leader cannot.
+    await withdrawOutbidFunds(marketplace, auction, alice);
+    expect(await tokenBalance(marketplace, alice.tokenAccount)).to.equal(10_000);
+    await expectError(withdrawOutbidFunds(marketplace, auction, bob), "WithdrawalNotAvailable");
+
+    await expectError(finalizeAuction(marketplace, auction), "AuctionNotEnded");
+    await warpBy(marketplace, MIN_AUCTION_DURATION + 1);
+    await finalizeAuction(marketplace, auction);
+    auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ endedSoldPayPending: {} });
+    expect(auctionAccount.clearingPriceUsdStar.toNumber()).to.equal(2_001);
+
+    // Only the winner may settle during the grace period.
+    await expectError(settleAuction(marketplace, auction, seller, bob, alice.keypair), "SettlementDeadlineNotReached");
+    await settleAuction(marketplace, auction, seller, bob);
+
+    const commission = Math.floor((2_001 * 500) / 10_000);
+    expect(await tokenBalance(marketplace, bob.tokenAccount)).to.equal(10_000 - 2_001);
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 - LISTING_FEE + 2_001 - commission);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(commission);
+    expect(await accountExists(marketplace, escrowPda(marketplace.program, auction))).to.be.false;
+
+    const assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.currentOwner.toBase58()).to.equal(bob.publicKey.toBase58());
+
+    // With the escrow closed, the remaining (empty) bid records are closed without it.
+    await withdrawOutbidFunds(marketplace, auction, bob, false);
+    await withdrawOutbidFunds(marketplace, auction, alice, false);
+    expect(await accountExists(marketplace, bidRecordPda(marketplace.program, auction, alice.publicKey))).to.be.false;
+    expect(await accountExists(marketplace, bidRecordPda(marketplace.program, auction, bob.publicKey))).to.be.false;
+  });
+
+  it("enforces the minimum increment and refuses bids below the start price", async () => {
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000, minBidIncrement: 100 });
+    await expectError(placeBid(marketplace, auction, alice, 999), "BidTooLow");
+    await placeBid(marketplace, auction, alice, 1_000);
+    await expectError(placeBid(marketplace, auction, bob, 1_050), "BidIncrementTooLow");
+    await placeBid(marketplace, auction, bob, 1_100);
+  });
+
+  it("extends the end time only for bids that move the price or the lead", async () => {
+    await updateConfig(marketplace, { antiSnipeWindowSeconds: 30, antiSnipeExtensionSeconds: 60 });
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000 });
+    await placeBid(marketplace, auction, alice, 1_500);
+    const originalEnd = (await marketplace.program.account.auctionAccount.fetch(auction)).auctionEndTimestamp.toNumber();
+
+    // The leader quietly raising their maximum inside the window does not extend the auction.
+    await warpTo(marketplace, originalEnd - 10);
+    await placeBid(marketplace, auction, alice, 3_000);
+    let auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionEndTimestamp.toNumber()).to.equal(originalEnd);
+
+    // A challenger moving the visible price does.
+    await placeBid(marketplace, auction, bob, 2_000);
+    auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionEndTimestamp.toNumber()).to.equal(originalEnd - 10 + 60);
+  });
+
+  it("emits the caller as the bidder when the leader's proxy answers", async () => {
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000 });
+    await placeBid(marketplace, auction, alice, 2_000);
+
+    const tx = await placeBidMethod(marketplace, auction, bob, 1_500).transaction();
+    const bidPlaced = findEvent(await sendAndCollectEvents(marketplace, tx, [bob.keypair]), "BidPlacedEvent");
+    expect(bidPlaced.bidder.toBase58()).to.equal(bob.publicKey.toBase58());
+    expect(bidPlaced.highestBidder.toBase58()).to.equal(alice.publicKey.toBase58());
+    expect(bidPlaced.isProxyBid).to.be.true;
+  });
+
+  it("ends the auction immediately at the buy-it-now price", async () => {
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000, buyNowPrice: 3_000 });
+    await placeBid(marketplace, auction, alice, 5_000);
+
+    const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ endedSoldPayPending: {} });
+    expect(auctionAccount.clearingPriceUsdStar.toNumber()).to.equal(3_000);
+    expect(await tokenBalance(marketplace, alice.tokenAccount)).to.equal(7_000);
+  });
+
+  it("returns the asset when the reserve is not met", async () => {
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000, reservePrice: 5_000 });
+    await placeBid(marketplace, auction, alice, 2_000);
+    await warpBy(marketplace, MIN_AUCTION_DURATION + 1);
+    await finalizeAuction(marketplace, auction);
+
+    const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ endedReserveNotMet: {} });
+    const assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.assetListedStatus).to.deep.equal({ readyForAuction: {} });
+
+    await withdrawOutbidFunds(marketplace, auction, alice);
+    expect(await tokenBalance(marketplace, alice.tokenAccount)).to.equal(10_000);
+    expect(await accountExists(marketplace, escrowPda(marketplace.program, auction))).to.be.false;
+  });
+
+  it("opens scheduled auctions on activate_auction", async () => {
+    const startTimestamp = (await now(marketplace)) + 120;
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startTimestamp });
+    let auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ pendingStart: {} });
+
+    const activate = () =>
+      marketplace.program.methods
+        .activateAuction()
+        .accountsPartial({ auctionAccount: auction, signer: marketplace.provider.wallet.publicKey })
+        .rpc();
+    await expectError(activate(), "AuctionNotStarted");
+    await expectError(placeBid(marketplace, auction, alice, 1_000), "AuctionNotStarted");
+
+    await warpTo(marketplace, startTimestamp);
+    await activate();
+    auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ active: {} });
+  });
+
+  it("lets a keeper finalize an ended auction and settle it once the winner's window lapses", async () => {
+    const keeper = await createUser(marketplace);
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000 });
+    await placeBid(marketplace, auction, alice, 1_000);
+    await warpBy(marketplace, MIN_AUCTION_DURATION + 1);
+
+    // Within the settlement window the crank only finalizes; settling is still the winner's call.
+    await crank(auction, keeper, alice);
+    let auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ endedSoldPayPending: {} });
+    await expectError(crank(auction, keeper, alice), "SettlementDeadlineNotReached");
+
+    await warpTo(marketplace, auctionAccount.settlementDeadlineTimestamp.toNumber());
+    await crank(auction, keeper, alice);
+    auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ completed: {} });
+    const assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.currentOwner.toBase58()).to.equal(alice.publicKey.toBase58());
+  });
+
+  it("unwinds an unsold auction through the keeper crank", async () => {
+    const keeper = await createUser(marketplace);
+    const auction = await listEnglishAuction(marketplace, seller, asset);
+    await warpBy(marketplace, MIN_AUCTION_DURATION + 1);
+
+    await crank(auction, keeper, null);
+    const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ endedUnsold: {} });
+    expect(await accountExists(marketplace, escrowPda(marketplace.program, auction))).to.be.false;
+  });
+
+  it("holds proceeds until the buyer confirms delivery", async () => {
+    await updateConfig(marketplace, { deliveryConfirmationPeriodSeconds: 600 });
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000 });
+    await placeBid(marketplace, auction, alice, 1_000);
+    await warpBy(marketplace, MIN_AUCTION_DURATION + 1);
+    await finalizeAuction(marketplace, auction);
+    await settleAuction(marketplace, auction, seller, alice);
+
+    let auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ awaitingDelivery: {} });
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 - LISTING_FEE);
+
+    await marketplace.program.methods
+      .confirmDelivery()
+      .accountsPartial({
+        auctionAccount: auction,
+        buyer: alice.publicKey,
+        assetAccount: asset,
+        marketplaceConfig: configPda(marketplace.program),
+        auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+        auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+        sellerTokenAccount: seller.tokenAccount,
+        treasuryPernTokenAccount: marketplace.treasury,
+      })
+      .signers([alice.keypair])
+      .rpc();
+
+    auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ completed: {} });
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 - LISTING_FEE + 950);
+  });
+
+  it("releases unconfirmed proceeds through the keeper crank after the delivery window", async () => {
+    await updateConfig(marketplace, { deliveryConfirmationPeriodSeconds: 600 });
+    const keeper = await createUser(marketplace);
+    const auction = await listEnglishAuction(marketplace, seller, asset, { startPrice: 1_000 });
+    await placeBid(marketplace, auction, alice, 1_000);
+    await warpBy(marketplace, MIN_AUCTION_DURATION + 1);
+    await finalizeAuction(marketplace, auction);
+    await settleAuction(marketplace, auction, seller, alice);
+
+    await expectError(crank(auction, keeper, alice), "DeliveryWindowOpen");
+    await warpBy(marketplace, 601);
+    await crank(auction, keeper, alice);
+    const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ completed: {} });
+  });
+
+  it("lets a pauser cancel a live auction and flag the asset", async () => {
+    const auction = await listEnglishAuction(marketplace, seller, asset);
+    await placeBid(marketplace, auction, alice, 1_000);
+
+    // Emergency cancels need the Arbiter or Pauser role; a plain Pauser key is enough.
+    await expectError(adminCancel(auction, bob.keypair), "Unauthorized");
+    await marketplace.program.methods
+      .grantRole({ pauser: {} }, bob.publicKey)
+      .accountsPartial({
+        marketplaceConfig: configPda(marketplace.program),
+        roleRegistry: roleRegistryPda(marketplace.program),
+        admin: marketplace.admin.publicKey,
+      })
+      .signers([marketplace.admin])
+      .rpc();
+    await adminCancel(auction, bob.keypair);
+
+    const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ cancelled: {} });
+    await withdrawOutbidFunds(marketplace, auction, alice);
+    expect(await tokenBalance(marketplace, alice.tokenAccount)).to.equal(10_000);
+
+    // By default the seller forfeits the listing fee to the treasury.
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(LISTING_FEE);
+    expect(await accountExists(marketplace, listingFeeVaultPda(marketplace.program, auction))).to.be.false;
+  });
+
+  it("refunds the listing fee on an emergency cancel when configured", async () => {
+    await updateConfig(marketplace, { refundListingFeeOnAdminCancel: true });
+    const auction = await listEnglishAuction(marketplace, seller, asset);
+    await placeBid(marketplace, auction, alice, 1_000);
+
+    await adminCancel(auction, marketplace.admin);
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(0);
+  });
+});
@@ -0,0 +1,122 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  LISTING_FEE,
+  Marketplace,
+  User,
+  accountExists,
+  configPda,
+  createUser,
+  createVerifiedAsset,
+  expectError,
+  fixedPriceListingPda,
+  roleRegistryPda,
+  setupMarketplace,
+  tokenBalance,
+} from "./helpers";
+
+describe("fixed-price listings", () => {
+  let marketplace: Marketplace;
+  let seller: User;
+  let buyer: User;
+  let asset: PublicKey;
+  let listing: PublicKey;
+
+  beforeEach(async () => {
+    marketplace = await setupMarketplace();
+    seller = await createUser(marketplace, 10_000);
+    buyer = await createUser(marketplace, 10_000);
+    asset = await createVerifiedAsset(marketplace, seller);
+    listing = fixedPriceListingPda(marketplace.program, asset);
+  });
+
+  async function listFixedPrice(price: number) {
+    await marketplace.program.methods
+      .listFixedPrice(new BN(price))
+      .accountsPartial({
+        fixedPriceListing: listing,
+        assetAccount: asset,
+        seller: seller.publicKey,
+        marketplaceConfig: configPda(marketplace.program),
+        sellerPernTokenAccount: seller.tokenAccount,
+        treasuryPernTokenAccount: marketplace.treasury,
+      })
+      .signers([seller.keypair])
+      .rpc();
+  }
+
+  async function buyFixedPrice(expectedPrice: number) {
+    await marketplace.program.methods
+      .buyFixedPrice(new BN(expectedPrice))
+      .accountsPartial({
+        fixedPriceListing: listing,
+        assetAccount: asset,
+        buyer: buyer.publicKey,
+        buyerPernTokenAccount: buyer.tokenAccount,
+        seller: seller.publicKey,
+        sellerTokenAccount: seller.tokenAccount,
+        marketplaceConfig: configPda(marketplace.program),
+        treasuryPernTokenAccount: marketplace.treasury,
+      })
+      .signers([buyer.keypair])
+      .rpc();
+  }
+
+  it("sells at the listed price and hands the asset over", async () => {
+    await listFixedPrice(4_000);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(LISTING_FEE);
+    let assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.assetListedStatus).to.deep.equal({ listedFixedPrice: {} });
+
+    await buyFixedPrice(4_000);
+    expect(await tokenBalance(marketplace, buyer.tokenAccount)).to.equal(6_000);
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 - LISTING_FEE + 3_800);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(LISTING_FEE + 200);
+    expect(await accountExists(marketplace, listing)).to.be.false;
+
+    assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.currentOwner.toBase58()).to.equal(buyer.publicKey.toBase58());
+  });
+
+  it("protects the buyer from a price change in flight", async () => {
+    await listFixedPrice(4_000);
+    await marketplace.program.methods
+      .updatePrice(new BN(5_000))
+      .accountsPartial({ fixedPriceListing: listing, seller: seller.publicKey })
+      .signers([seller.keypair])
+      .rpc();
+
+    await expectError(buyFixedPrice(4_000), "FixedPriceChanged");
+    await buyFixedPrice(5_000);
+  });
+
+  it("returns the asset on delist", async () => {
+    await listFixedPrice(4_000);
+    await marketplace.program.methods
+      .delist()
+      .accountsPartial({ fixedPriceListing: listing, assetAccount: asset, seller: seller.publicKey })
+      .signers([seller.keypair])
+      .rpc();
+
+    expect(await accountExists(marketplace, listing)).to.be.false;
+    const assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.assetListedStatus).to.deep.equal({ readyForAuction: {} });
+  });
+
+  it("blocks re-verifying a listed asset", async () => {
+    await listFixedPrice(4_000);
+    await expectError(
+      marketplace.program.methods
+        .adminUpdateOwnershipVerification({ rejected: {} }, null)
+        .accountsPartial({
+          assetAccount: asset,
+          roleRegistry: roleRegistryPda(marketplace.program),
+          verifier: marketplace.admin.publicKey,
+        })
+        .signers([marketplace.admin])
+        .rpc(),
+      "InvalidAssetStatus"
+    );
+  });
+});
@@ -0,0 +1,101 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  CONFIG_CHANGE_DELAY,
+  Marketplace,
+  accountExists,
+  configPda,
+  executeConfigChange,
+  expectError,
+  fundedKeypair,
+  proposeConfigChange,
+  setupMarketplace,
+  warpBy,
+} from "./helpers";
+
+describe("config governance", () => {
+  let marketplace: Marketplace;
+
+  beforeEach(async () => {
+    marketplace = await setupMarketplace();
+  });
+
+  async function approve(proposal: PublicKey, signer: Keypair) {
+    await marketplace.program.methods
+      .approveConfigChange()
+      .accountsPartial({
+        configProposal: proposal,
+        marketplaceConfig: configPda(marketplace.program),
+        signer: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function cancel(proposal: PublicKey, signer: Keypair) {
+    const { proposer } = await marketplace.program.account.configProposal.fetch(proposal);
+    await marketplace.program.methods
+      .cancelConfigChange()
+      .accountsPartial({ configProposal: proposal, proposer, signer: signer.publicKey })
+      .signers([signer])
+      .rpc();
+  }
+
+  const fetchConfig = () => marketplace.program.account.marketplaceConfig.fetch(configPda(marketplace.program));
+
+  it("applies a fee change only after the timelock", async () => {
+    const proposal = await proposeConfigChange(marketplace, { listingFee: { listingFeeUsdStar: new BN(2_500) } });
+
+    await expectError(executeConfigChange(marketplace, proposal), "ConfigChangeNotYetEffective");
+    await warpBy(marketplace, CONFIG_CHANGE_DELAY);
+    await executeConfigChange(marketplace, proposal);
+
+    expect((await fetchConfig()).listingFeeUsdStar.toNumber()).to.equal(2_500);
+    expect(await accountExists(marketplace, proposal)).to.be.false;
+  });
+
+  it("requires the signer threshold once the signer set grows", async () => {
+    const cosigner = await fundedKeypair(marketplace);
+    const signersProposal = await proposeConfigChange(marketplace, {
+      configSigners: { signers: [marketplace.admin.publicKey, cosigner.publicKey], threshold: 2 },
+    });
+    await warpBy(marketplace, CONFIG_CHANGE_DELAY);
+    await executeConfigChange(marketplace, signersProposal);
+    expect((await fetchConfig()).configApprovalThreshold).to.equal(2);
+
+    const feeProposal = await proposeConfigChange(marketplace, { saleCommission: { saleCommissionBps: 300 } });
+    await warpBy(marketplace, CONFIG_CHANGE_DELAY);
+    await expectError(executeConfigChange(marketplace, feeProposal), "InsufficientApprovals");
+
+    await expectError(approve(feeProposal, marketplace.admin), "ProposalAlreadyApproved");
+    await approve(feeProposal, cosigner);
+    await executeConfigChange(marketplace, feeProposal);
+    expect((await fetchConfig()).saleCommissionBps).to.equal(300);
+  });
+
+  it("keeps outsiders out of signer-only changes and approvals", async () => {
+    const outsider = await fundedKeypair(marketplace);
+    await expectError(
+      proposeConfigChange(marketplace, { changeDelay: { configChangeDelaySeconds: new BN(0) } }, outsider),
+      "NotConfigSigner"
+    );
+    // Fee changes also need the FeeManager role, which only the admin holds.
+    await expectError(
+      proposeConfigChange(marketplace, { listingFee: { listingFeeUsdStar: new BN(0) } }, outsider),
+      "Unauthorized"
+    );
+
+    const proposal = await proposeConfigChange(marketplace, { keeperFee: { keeperFeeBps: 1_000 } });
+    await expectError(approve(proposal, outsider), "NotConfigSigner");
+  });
+
+  it("lets only the proposer cancel before expiry", async () => {
+    const proposal = await proposeConfigChange(marketplace, { keeperFee: { keeperFeeBps: 1_000 } });
+    const outsider = await fundedKeypair(marketplace);
+
+    await expectError(cancel(proposal, outsider), "ProposalNotExpired");
+    await cancel(proposal, marketplace.admin);
+    expect(await accountExists(marketplace, proposal)).to.be.false;
+  });
+});
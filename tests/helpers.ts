@@ -0,0 +1,530 @@
+import { BN, EventParser, Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  AccountLayout,
+  MINT_SIZE,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMint2Instruction,
+  createMintToInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { createHash } from "crypto";
+import { expect } from "chai";
+import { DubuuMarketplaceMvp } from "../target/types/dubuu_marketplace_mvp";
+
+const IDL = require("../target/idl/dubuu_marketplace_mvp.json");
+
+// Integration tests run against an in-process bank (solana-bankrun) so they can move
+// the clock past auction ends, settlement windows and governance timelocks.
+
+export const LISTING_FEE = 1_000;
+export const SALE_COMMISSION_BPS = 500;
+export const MIN_AUCTION_DURATION = 60;
+export const SETTLEMENT_GRACE_PERIOD = 3_600;
+
+export interface Marketplace {
+  context: ProgramTestContext;
+  provider: BankrunProvider;
+  program: Program<DubuuMarketplaceMvp>;
+  admin: Keypair;
+  mint: PublicKey;
+  mintAuthority: Keypair;
+  treasuryOwner: Keypair;
+  treasury: PublicKey;
+}
+
+export interface User {
+  keypair: Keypair;
+  publicKey: PublicKey;
+  tokenAccount: PublicKey;
+}
+
+// --- PDAs ---
+
+export function pda(program: Program<DubuuMarketplaceMvp>, seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+}
+
+export function u64Le(value: number): Buffer {
+  return new BN(value).toArrayLike(Buffer, "le", 8);
+}
+
+export const configPda = (program: Program<DubuuMarketplaceMvp>) =>
+  pda(program, [Buffer.from("marketplace_config")]);
+export const roleRegistryPda = (program: Program<DubuuMarketplaceMvp>) =>
+  pda(program, [Buffer.from("role_registry")]);
+export const assetPda = (program: Program<DubuuMarketplaceMvp>, assetIdSeed: string) =>
+  pda(program, [Buffer.from("asset"), createHash("sha256").update(assetIdSeed).digest().subarray(0, 5)]);
+export const auctionPda = (program: Program<DubuuMarketplaceMvp>, asset: PublicKey, listingNonce: number) =>
+  pda(program, [Buffer.from("auction"), asset.toBuffer(), u64Le(listingNonce)]);
+export const escrowPda = (program: Program<DubuuMarketplaceMvp>, auction: PublicKey) =>
+  pda(program, [Buffer.from("escrow"), auction.toBuffer()]);
+export const escrowAuthorityPda = (program: Program<DubuuMarketplaceMvp>, auction: PublicKey) =>
+  pda(program, [Buffer.from("escrow_authority"), auction.toBuffer()]);
+export const listingFeeVaultPda = (program: Program<DubuuMarketplaceMvp>, auction: PublicKey) =>
+  pda(program, [Buffer.from("listing_fee_vault"), auction.toBuffer()]);
+export const bidRecordPda = (program: Program<DubuuMarketplaceMvp>, auction: PublicKey, bidder: PublicKey) =>
+  pda(program, [Buffer.from("bid_record"), auction.toBuffer(), bidder.toBuffer()]);
+export const disputePda = (program: Program<DubuuMarketplaceMvp>, auction: PublicKey) =>
+  pda(program, [Buffer.from("dispute"), auction.toBuffer()]);
+export const fixedPriceListingPda = (program: Program<DubuuMarketplaceMvp>, asset: PublicKey) =>
+  pda(program, [Buffer.from("fixed_price_listing"), asset.toBuffer()]);
+export const offerPda = (program: Program<DubuuMarketplaceMvp>, asset: PublicKey, buyer: PublicKey) =>
+  pda(program, [Buffer.from("offer"), asset.toBuffer(), buyer.toBuffer()]);
+export const offerEscrowPda = (program: Program<DubuuMarketplaceMvp>, offer: PublicKey) =>
+  pda(program, [Buffer.from("offer_escrow"), offer.toBuffer()]);
+export const offerEscrowAuthorityPda = (program: Program<DubuuMarketplaceMvp>, offer: PublicKey) =>
+  pda(program, [Buffer.from("offer_escrow_authority"), offer.toBuffer()]);
+export const configProposalPda = (program: Program<DubuuMarketplaceMvp>, proposalId: number) =>
+  pda(program, [Buffer.from("config_proposal"), u64Le(proposalId)]);
+
+// --- Bank helpers ---
+
+export async function now(marketplace: Marketplace): Promise<number> {
+  const clock = await marketplace.context.banksClient.getClock();
+  return Number(clock.unixTimestamp);
+}
+
+export async function warpTo(marketplace: Marketplace, unixTimestamp: number): Promise<void> {
+  const clock = await marketplace.context.banksClient.getClock();
+  marketplace.context.setClock(
+    new Clock(
+      clock.slot + BigInt(1),
+      clock.epochStartTimestamp,
+      clock.epoch,
+      clock.leaderScheduleEpoch,
+      BigInt(unixTimestamp)
+    )
+  );
+}
+
+export async function warpBy(marketplace: Marketplace, seconds: number): Promise<void> {
+  await warpTo(marketplace, (await now(marketplace)) + seconds);
+}
+
+export async function accountExists(marketplace: Marketplace, address: PublicKey): Promise<boolean> {
+  return (await marketplace.context.banksClient.getAccount(address)) !== null;
+}
+
+// Rewrites an account as a pre-upgrade deployment left it: the trailing fields it lacked are dropped.
+export async function truncateAccount(marketplace: Marketplace, address: PublicKey, bytes: number): Promise<void> {
+  const account = await marketplace.context.banksClient.getAccount(address);
+  marketplace.context.setAccount(address, {
+    ...account,
+    data: Buffer.from(account.data).subarray(0, account.data.length - bytes),
+  });
+}
+
+export async function tokenBalance(marketplace: Marketplace, tokenAccount: PublicKey): Promise<number> {
+  const account = await marketplace.context.banksClient.getAccount(tokenAccount);
+  if (account === null) {
+    return 0;
+  }
+  return Number(AccountLayout.decode(Buffer.from(account.data)).amount);
+}
+
+// Asserts the call fails with the named program error (matched on its custom error code).
+export async function expectError(call: Promise<unknown>, errorName: string): Promise<void> {
+  const idlError = (IDL.errors as { code: number; name: string }[]).find(
+    (error) => error.name.toLowerCase() === errorName.toLowerCase()
+  );
+  expect(idlError, `unknown error ${errorName}`).to.not.be.undefined;
+  try {
+    await call;
+  } catch (error) {
+    expect(String(error)).to.contain(`0x${idlError.code.toString(16)}`);
+    return;
+  }
+  expect.fail(`expected ${errorName}`);
+}
+
+// Sends the transaction directly to the bank and decodes the program events from its logs.
+export async function sendAndCollectEvents(
+  marketplace: Marketplace,
+  tx: Transaction,
+  signers: Keypair[]
+): Promise<{ name: string; data: any }[]> {
+  const { context, program } = marketplace;
+  tx.recentBlockhash = context.lastBlockhash;
+  tx.feePayer = context.payer.publicKey;
+  tx.sign(context.payer, ...signers);
+  const meta = await context.banksClient.processTransaction(tx);
+  const parser = new EventParser(program.programId, program.coder);
+  return Array.from(parser.parseLogs(meta.logMessages));
+}
+
+export function findEvent(events: { name: string; data: any }[], eventName: string): any {
+  const event = events.find((candidate) => candidate.name.toLowerCase() === eventName.toLowerCase());
+  expect(event, `missing ${eventName}`).to.not.be.undefined;
+  return event.data;
+}
+
+export async function fundedKeypair(marketplace: Marketplace): Promise<Keypair> {
+  const keypair = Keypair.generate();
+  marketplace.context.setAccount(keypair.publicKey, {
+    lamports: 100 * LAMPORTS_PER_SOL,
+    data: Buffer.alloc(0),
+    owner: SystemProgram.programId,
+    executable: false,
+  });
+  return keypair;
+}
+
+export async function createUser(marketplace: Marketplace, tokens = 0): Promise<User> {
+  const keypair = await fundedKeypair(marketplace);
+  const tokenAccount = await createTokenAccount(marketplace, keypair.publicKey);
+  if (tokens > 0) {
+    await mintTo(marketplace, tokenAccount, tokens);
+  }
+  return { keypair, publicKey: keypair.publicKey, tokenAccount };
+}
+
+export async function createTokenAccount(marketplace: Marketplace, owner: PublicKey): Promise<PublicKey> {
+  const tokenAccount = getAssociatedTokenAddressSync(marketplace.mint, owner);
+  const tx = new Transaction().add(
+    createAssociatedTokenAccountInstruction(
+      marketplace.provider.wallet.publicKey,
+      tokenAccount,
+      owner,
+      marketplace.mint
+    )
+  );
+  await marketplace.provider.sendAndConfirm(tx);
+  return tokenAccount;
+}
+
+export async function mintTo(marketplace: Marketplace, tokenAccount: PublicKey, amount: number): Promise<void> {
+  const tx = new Transaction().add(
+    createMintToInstruction(marketplace.mint, tokenAccount, marketplace.mintAuthority.publicKey, amount)
+  );
+  await marketplace.provider.sendAndConfirm(tx, [marketplace.mintAuthority]);
+}
+
+// --- Marketplace fixtures ---
+
+export interface ConfigUpdate {
+  pausedStatus?: boolean;
+  antiSnipeWindowSeconds?: number;
+  antiSnipeExtensionSeconds?: number;
+  minBidIncrementUsdStar?: number;
+  minBidIncrementBps?: number;
+  refundListingFeeOnCancel?: boolean;
+  maxAuctionStartDelaySeconds?: number;
+  minAuctionDurationSeconds?: number;
+  maxAuctionDurationSeconds?: number;
+  sealedBidSlashBps?: number;
+  settlementGracePeriodSeconds?: number;
+  deliveryConfirmationPeriodSeconds?: number;
+  refundListingFeeOnAdminCancel?: boolean;
+}
+
+const bnOrNull = (value?: number) => (value === undefined ? null : new BN(value));
+const valueOrNull = <T>(value?: T) => (value === undefined ? null : value);
+
+export async function updateConfig(
+  marketplace: Marketplace,
+  update: ConfigUpdate,
+  authority: Keypair = marketplace.admin
+): Promise<void> {
+  await marketplace.program.methods
+    .updateConfig(
+      valueOrNull(update.pausedStatus),
+      bnOrNull(update.antiSnipeWindowSeconds),
+      bnOrNull(update.antiSnipeExtensionSeconds),
+      bnOrNull(update.minBidIncrementUsdStar),
+      valueOrNull(update.minBidIncrementBps),
+      valueOrNull(update.refundListingFeeOnCancel),
+      bnOrNull(update.maxAuctionStartDelaySeconds),
+      bnOrNull(update.minAuctionDurationSeconds),
+      bnOrNull(update.maxAuctionDurationSeconds),
+      valueOrNull(update.sealedBidSlashBps),
+      bnOrNull(update.settlementGracePeriodSeconds),
+      bnOrNull(update.deliveryConfirmationPeriodSeconds),
+      valueOrNull(update.refundListingFeeOnAdminCancel)
+    )
+    .accountsPartial({
+      marketplaceConfig: configPda(marketplace.program),
+      roleRegistry: roleRegistryPda(marketplace.program),
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+}
+
+// Starts a fresh bank with the program deployed, a PERN mint and an initialized marketplace
+// whose durations are shortened so tests only need to warp the clock by minutes.
+export async function setupMarketplace(): Promise<Marketplace> {
+  const context = await startAnchor(".", [], []);
+  const provider = new BankrunProvider(context);
+  const program = new Program<DubuuMarketplaceMvp>(IDL as DubuuMarketplaceMvp, provider);
+
+  const partial = { context, provider, program } as Marketplace;
+  partial.admin = await fundedKeypair(partial);
+  partial.mintAuthority = await fundedKeypair(partial);
+  partial.treasuryOwner = await fundedKeypair(partial);
+
+  const mint = Keypair.generate();
+  const rent = await context.banksClient.getRent();
+  const createMintTx = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: provider.wallet.publicKey,
+      newAccountPubkey: mint.publicKey,
+      lamports: Number(rent.minimumBalance(BigInt(MINT_SIZE))),
+      space: MINT_SIZE,
+      programId: TOKEN_PROGRAM_ID,
+    }),
+    createInitializeMint2Instruction(mint.publicKey, 6, partial.mintAuthority.publicKey, null)
+  );
+  await provider.sendAndConfirm(createMintTx, [mint]);
+  partial.mint = mint.publicKey;
+  partial.treasury = await createTokenAccount(partial, partial.treasuryOwner.publicKey);
+
+  await program.methods
+    .initializeConfig(partial.admin.publicKey, partial.treasury, new BN(LISTING_FEE), SALE_COMMISSION_BPS)
+    .accountsPartial({
+      marketplaceConfig: configPda(program),
+      pernUsdStarMint: partial.mint,
+      roleRegistry: roleRegistryPda(program),
+      signer: provider.wallet.publicKey,
+    })
+    .rpc();
+
+  await updateConfig(partial, {
+    minAuctionDurationSeconds: MIN_AUCTION_DURATION,
+    settlementGracePeriodSeconds: SETTLEMENT_GRACE_PERIOD,
+  });
+
+  return partial;
+}
+
+let assetCounter = 0;
+
+export async function registerAsset(marketplace: Marketplace, owner: User): Promise<PublicKey> {
+  const assetIdSeed = `asset-${Date.now()}-${assetCounter++}`;
+  const asset = assetPda(marketplace.program, assetIdSeed);
+  await marketplace.program.methods
+    .registerAssetAndSubmitDocsRef(assetIdSeed, "bafy-test-metadata")
+    .accountsPartial({ assetAccount: asset, signer: owner.publicKey })
+    .signers([owner.keypair])
+    .rpc();
+  return asset;
+}
+
+// The admin holds the Verifier role from initialization, so it verifies by default.
+export async function verifyAsset(
+  marketplace: Marketplace,
+  asset: PublicKey,
+  verifier: Keypair = marketplace.admin
+): Promise<void> {
+  await marketplace.program.methods
+    .adminUpdateOwnershipVerification({ verified: {} }, null)
+    .accountsPartial({
+      assetAccount: asset,
+      roleRegistry: roleRegistryPda(marketplace.program),
+      verifier: verifier.publicKey,
+    })
+    .signers([verifier])
+    .rpc();
+}
+
+export async function createVerifiedAsset(marketplace: Marketplace, owner: User): Promise<PublicKey> {
+  const asset = await registerAsset(marketplace, owner);
+  await verifyAsset(marketplace, asset);
+  return asset;
+}
+
+// Accounts shared by the three list_asset_for_* instructions.
+export async function listingAccounts(marketplace: Marketplace, seller: User, asset: PublicKey) {
+  const { auctionCount } = await marketplace.program.account.assetAccount.fetch(asset);
+  const auction = auctionPda(marketplace.program, asset, auctionCount.toNumber());
+  return {
+    auction,
+    accounts: {
+      auctionAccount: auction,
+      assetAccount: asset,
+      seller: seller.publicKey,
+      marketplaceConfig: configPda(marketplace.program),
+      sellerPernTokenAccount: seller.tokenAccount,
+      auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+      listingFeeVault: listingFeeVaultPda(marketplace.program, auction),
+      pernUsdStarMintAccount: marketplace.mint,
+      auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+    },
+  };
+}
+
+export interface EnglishAuctionOptions {
+  startPrice?: number;
+  durationSeconds?: number;
+  reservePrice?: number;
+  minBidIncrement?: number;
+  buyNowPrice?: number;
+  startTimestamp?: number;
+}
+
+export async function listEnglishAuction(
+  marketplace: Marketplace,
+  seller: User,
+  asset: PublicKey,
+  options: EnglishAuctionOptions = {}
+): Promise<PublicKey> {
+  const { auction, accounts } = await listingAccounts(marketplace, seller, asset);
+  await marketplace.program.methods
+    .listAssetForAuction(
+      new BN(options.startPrice ?? 1_000),
+      new BN(options.durationSeconds ?? MIN_AUCTION_DURATION),
+      bnOrNull(options.reservePrice),
+      false,
+      bnOrNull(options.minBidIncrement),
+      null,
+      bnOrNull(options.buyNowPrice),
+      bnOrNull(options.startTimestamp)
+    )
+    .accountsPartial(accounts)
+    .signers([seller.keypair])
+    .rpc();
+  return auction;
+}
+
+export function placeBidMethod(marketplace: Marketplace, auction: PublicKey, bidder: User, maxBid: number) {
+  return marketplace.program.methods
+    .placeBid(new BN(maxBid))
+    .accountsPartial({
+      auctionAccount: auction,
+      bidder: bidder.publicKey,
+      bidderPernTokenAccount: bidder.tokenAccount,
+      auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+      auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+      bidRecord: bidRecordPda(marketplace.program, auction, bidder.publicKey),
+    })
+    .signers([bidder.keypair]);
+}
+
+export async function placeBid(
+  marketplace: Marketplace,
+  auction: PublicKey,
+  bidder: User,
+  maxBid: number
+): Promise<void> {
+  await placeBidMethod(marketplace, auction, bidder, maxBid).rpc();
+}
+
+export async function finalizeAuction(marketplace: Marketplace, auction: PublicKey): Promise<void> {
+  const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+  await marketplace.program.methods
+    .finalizeAuction()
+    .accountsPartial({
+      auctionAccount: auction,
+      signer: marketplace.provider.wallet.publicKey,
+      assetAccount: auctionAccount.assetKey,
+      auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+      auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+      sellerRentRecipient: auctionAccount.seller,
+    })
+    .rpc();
+}
+
+export async function settleAuction(
+  marketplace: Marketplace,
+  auction: PublicKey,
+  seller: User,
+  winner: User,
+  settler: Keypair = winner.keypair
+): Promise<void> {
+  const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+  await marketplace.program.methods
+    .settleAuctionAndTransfer()
+    .accountsPartial({
+      auctionAccount: auction,
+      highestBidder: winner.publicKey,
+      settler: settler.publicKey,
+      assetAccount: auctionAccount.assetKey,
+      marketplaceConfig: configPda(marketplace.program),
+      auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+      auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+      sellerTokenAccount: seller.tokenAccount,
+      treasuryPernTokenAccount: marketplace.treasury,
+      highestBidderTokenAccount: winner.tokenAccount,
+    })
+    .signers([settler])
+    .rpc();
+}
+
+export async function withdrawOutbidFunds(
+  marketplace: Marketplace,
+  auction: PublicKey,
+  bidder: User,
+  withEscrow = true
+): Promise<void> {
+  const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+  await marketplace.program.methods
+    .withdrawOutbidFunds()
+    .accountsPartial({
+      auctionAccount: auction,
+      bidRecord: bidRecordPda(marketplace.program, auction, bidder.publicKey),
+      bidder: bidder.publicKey,
+      signer: marketplace.provider.wallet.publicKey,
+      bidderPernTokenAccount: bidder.tokenAccount,
+      auctionEscrowTokenAccount: withEscrow ? escrowPda(marketplace.program, auction) : null,
+      auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+      sellerRentRecipient: auctionAccount.seller,
+    })
+    .rpc();
+}
+
+// Commitment checked by reveal_sealed_bid: sha256(auction || bidder || amount_le || salt).
+export function sealedBidCommitment(auction: PublicKey, bidder: PublicKey, amount: number, salt: Buffer): number[] {
+  return Array.from(
+    createHash("sha256")
+      .update(Buffer.concat([auction.toBuffer(), bidder.toBuffer(), u64Le(amount), salt]))
+      .digest()
+  );
+}
+
+// --- Governance ---
+
+export const CONFIG_CHANGE_DELAY = 2 * 24 * 60 * 60;
+
+// Proposes `change` to take effect as soon as the timelock allows; returns the proposal address.
+export async function proposeConfigChange(
+  marketplace: Marketplace,
+  change: any,
+  proposer: Keypair = marketplace.admin
+): Promise<PublicKey> {
+  const config = await marketplace.program.account.marketplaceConfig.fetch(configPda(marketplace.program));
+  const proposal = configProposalPda(marketplace.program, config.configProposalCount.toNumber());
+  const effectiveAt = (await now(marketplace)) + config.configChangeDelaySeconds.toNumber();
+  await marketplace.program.methods
+    .proposeConfigChange(change, new BN(effectiveAt), new BN(effectiveAt + 24 * 60 * 60))
+    .accountsPartial({
+      configProposal: proposal,
+      marketplaceConfig: configPda(marketplace.program),
+      roleRegistry: roleRegistryPda(marketplace.program),
+      proposer: proposer.publicKey,
+    })
+    .signers([proposer])
+    .rpc();
+  return proposal;
+}
+
+export async function executeConfigChange(marketplace: Marketplace, proposal: PublicKey): Promise<void> {
+  const configProposal = await marketplace.program.account.configProposal.fetch(proposal);
+  await marketplace.program.methods
+    .executeConfigChange()
+    .accountsPartial({
+      configProposal: proposal,
+      marketplaceConfig: configPda(marketplace.program),
+      proposer: configProposal.proposer,
+      signer: marketplace.provider.wallet.publicKey,
+    })
+    .rpc();
+}
@@ -0,0 +1,91 @@
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Marketplace,
+  User,
+  configPda,
+  createUser,
+  createVerifiedAsset,
+  expectError,
+  fundedKeypair,
+  listEnglishAuction,
+  roleRegistryPda,
+  setupMarketplace,
+  truncateAccount,
+} from "./helpers";
+
+describe("account migrations", () => {
+  let marketplace: Marketplace;
+  let seller: User;
+
+  beforeEach(async () => {
+    marketplace = await setupMarketplace();
+    seller = await createUser(marketplace, 10_000);
+  });
+
+  it("grows an asset registered before auction_count so it can be listed again", async () => {
+    const asset = await createVerifiedAsset(marketplace, seller);
+    await truncateAccount(marketplace, asset, 8);
+
+    const migrateAsset = () =>
+      marketplace.program.methods
+        .migrateAsset()
+        .accountsPartial({ assetAccount: asset, payer: marketplace.provider.wallet.publicKey })
+        .rpc();
+
+    await migrateAsset();
+    const assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.auctionCount.toNumber()).to.equal(0);
+    await expectError(migrateAsset(), "AccountNotMigratable");
+
+    await listEnglishAuction(marketplace, seller, asset);
+    expect((await marketplace.program.account.assetAccount.fetch(asset)).auctionCount.toNumber()).to.equal(1);
+  });
+
+  it("grows a config deployed before the role registry and fills in the defaults", async () => {
+    // Simulate an older deployment: only the fields up to `bump`, and no registry.
+    const LEGACY_CONFIG_SPACE = 8 + 32 + 32 + 32 + 8 + 2 + 1 + 1;
+    const config = configPda(marketplace.program);
+    const { data } = await marketplace.context.banksClient.getAccount(config);
+    await truncateAccount(marketplace, config, data.length - LEGACY_CONFIG_SPACE);
+    marketplace.context.setAccount(roleRegistryPda(marketplace.program), {
+      lamports: 0,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+
+    const migrateConfig = (admin: Keypair) =>
+      marketplace.program.methods
+        .migrateConfig()
+        .accountsPartial({
+          marketplaceConfig: config,
+          roleRegistry: roleRegistryPda(marketplace.program),
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+    await expectError(migrateConfig(await fundedKeypair(marketplace)), "Unauthorized");
+    await migrateConfig(marketplace.admin);
+
+    const migrated = await marketplace.program.account.marketplaceConfig.fetch(config);
+    expect(migrated.admin.toBase58()).to.equal(marketplace.admin.publicKey.toBase58());
+    expect(migrated.configSigners.map((signer) => signer.toBase58())).to.deep.equal([
+      marketplace.admin.publicKey.toBase58(),
+    ]);
+    expect(migrated.configApprovalThreshold).to.equal(1);
+    expect(migrated.configChangeDelaySeconds.toNumber()).to.equal(2 * 24 * 60 * 60);
+    expect(migrated.minAuctionDurationSeconds.toNumber()).to.equal(60 * 60);
+    expect(migrated.maxAuctionDurationSeconds.toNumber()).to.equal(30 * 24 * 60 * 60);
+    expect(migrated.settlementGracePeriodSeconds.toNumber()).to.equal(7 * 24 * 60 * 60);
+    expect(migrated.pendingAdmin).to.be.null;
+
+    const { assignments } = await marketplace.program.account.roleRegistry.fetch(roleRegistryPda(marketplace.program));
+    expect(assignments).to.have.length(5);
+    expect(assignments.every((assignment) => assignment.holder.equals(marketplace.admin.publicKey))).to.be.true;
+    await expectError(migrateConfig(marketplace.admin), "AccountNotMigratable");
+
+    await listEnglishAuction(marketplace, seller, await createVerifiedAsset(marketplace, seller));
+  });
+});
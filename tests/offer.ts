@@ -0,0 +1,158 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  CONFIG_CHANGE_DELAY,
+  Marketplace,
+  User,
+  accountExists,
+  configPda,
+  createUser,
+  createVerifiedAsset,
+  executeConfigChange,
+  expectError,
+  now,
+  offerEscrowAuthorityPda,
+  offerEscrowPda,
+  offerPda,
+  proposeConfigChange,
+  setupMarketplace,
+  tokenBalance,
+  warpBy,
+  warpTo,
+} from "./helpers";
+
+describe("offers", () => {
+  let marketplace: Marketplace;
+  let seller: User;
+  let buyer: User;
+  let asset: PublicKey;
+  let offer: PublicKey;
+
+  beforeEach(async () => {
+    marketplace = await setupMarketplace();
+    seller = await createUser(marketplace, 10_000);
+    buyer = await createUser(marketplace, 10_000);
+    asset = await createVerifiedAsset(marketplace, seller);
+    offer = offerPda(marketplace.program, asset, buyer.publicKey);
+  });
+
+  async function makeOffer(amount: number, expiryTimestamp: number) {
+    await marketplace.program.methods
+      .makeOffer(new BN(amount), new BN(expiryTimestamp))
+      .accountsPartial({
+        offerAccount: offer,
+        assetAccount: asset,
+        buyer: buyer.publicKey,
+        marketplaceConfig: configPda(marketplace.program),
+        buyerPernTokenAccount: buyer.tokenAccount,
+        offerEscrowTokenAccount: offerEscrowPda(marketplace.program, offer),
+        pernUsdStarMintAccount: marketplace.mint,
+        offerEscrowAuthority: offerEscrowAuthorityPda(marketplace.program, offer),
+      })
+      .signers([buyer.keypair])
+      .rpc();
+  }
+
+  async function acceptOffer() {
+    await marketplace.program.methods
+      .acceptOffer()
+      .accountsPartial({
+        offerAccount: offer,
+        assetAccount: asset,
+        seller: seller.publicKey,
+        sellerPernTokenAccount: seller.tokenAccount,
+        buyer: buyer.publicKey,
+        offerEscrowTokenAccount: offerEscrowPda(marketplace.program, offer),
+        offerEscrowAuthority: offerEscrowAuthorityPda(marketplace.program, offer),
+        marketplaceConfig: configPda(marketplace.program),
+        treasuryPernTokenAccount: marketplace.treasury,
+      })
+      .signers([seller.keypair])
+      .rpc();
+  }
+
+  async function cancelOffer(signer: Keypair) {
+    await marketplace.program.methods
+      .cancelOffer()
+      .accountsPartial({
+        offerAccount: offer,
+        buyer: buyer.publicKey,
+        signer: signer.publicKey,
+        buyerPernTokenAccount: buyer.tokenAccount,
+        offerEscrowTokenAccount: offerEscrowPda(marketplace.program, offer),
+        offerEscrowAuthority: offerEscrowAuthorityPda(marketplace.program, offer),
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  it("escrows the offer and pays the seller on acceptance", async () => {
+    await makeOffer(3_000, (await now(marketplace)) + 3_600);
+    expect(await tokenBalance(marketplace, offerEscrowPda(marketplace.program, offer))).to.equal(3_000);
+
+    await acceptOffer();
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 + 2_850);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(150);
+    expect(await accountExists(marketplace, offer)).to.be.false;
+    expect(await accountExists(marketplace, offerEscrowPda(marketplace.program, offer))).to.be.false;
+
+    const assetAccount = await marketplace.program.account.assetAccount.fetch(asset);
+    expect(assetAccount.currentOwner.toBase58()).to.equal(buyer.publicKey.toBase58());
+  });
+
+  it("charges the commission in force when the offer was made", async () => {
+    await makeOffer(3_000, (await now(marketplace)) + 2 * CONFIG_CHANGE_DELAY);
+
+    const proposal = await proposeConfigChange(marketplace, { saleCommission: { saleCommissionBps: 1_000 } });
+    await warpBy(marketplace, CONFIG_CHANGE_DELAY);
+    await executeConfigChange(marketplace, proposal);
+
+    await acceptOffer();
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 + 2_850);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(150);
+  });
+
+  it("refuses offers on your own asset and past expiries", async () => {
+    await expectError(makeOffer(3_000, (await now(marketplace)) - 1), "InvalidOfferExpiry");
+
+    offer = offerPda(marketplace.program, asset, seller.publicKey);
+    await expectError(
+      marketplace.program.methods
+        .makeOffer(new BN(3_000), new BN((await now(marketplace)) + 3_600))
+        .accountsPartial({
+          offerAccount: offer,
+          assetAccount: asset,
+          buyer: seller.publicKey,
+          marketplaceConfig: configPda(marketplace.program),
+          buyerPernTokenAccount: seller.tokenAccount,
+          offerEscrowTokenAccount: offerEscrowPda(marketplace.program, offer),
+          pernUsdStarMintAccount: marketplace.mint,
+          offerEscrowAuthority: offerEscrowAuthorityPda(marketplace.program, offer),
+        })
+        .signers([seller.keypair])
+        .rpc(),
+      "CannotOfferOnOwnAsset"
+    );
+  });
+
+  it("lets the buyer withdraw an offer at any time", async () => {
+    await makeOffer(3_000, (await now(marketplace)) + 3_600);
+    await cancelOffer(buyer.keypair);
+    expect(await tokenBalance(marketplace, buyer.tokenAccount)).to.equal(10_000);
+    expect(await accountExists(marketplace, offer)).to.be.false;
+  });
+
+  it("lets anyone return an expired offer and stops the seller accepting it", async () => {
+    const expiryTimestamp = (await now(marketplace)) + 3_600;
+    await makeOffer(3_000, expiryTimestamp);
+
+    const stranger = Keypair.generate();
+    await expectError(cancelOffer(stranger), "OfferNotExpired");
+
+    await warpTo(marketplace, expiryTimestamp);
+    await expectError(acceptOffer(), "OfferExpired");
+    await cancelOffer(stranger);
+    expect(await tokenBalance(marketplace, buyer.tokenAccount)).to.equal(10_000);
+  });
+});
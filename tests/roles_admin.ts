@@ -0,0 +1,146 @@
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  CONFIG_CHANGE_DELAY,
+  Marketplace,
+  configPda,
+  configProposalPda,
+  createUser,
+  executeConfigChange,
+  expectError,
+  fundedKeypair,
+  proposeConfigChange,
+  registerAsset,
+  roleRegistryPda,
+  setupMarketplace,
+  updateConfig,
+  verifyAsset,
+  warpBy,
+} from "./helpers";
+
+describe("roles and admin handover", () => {
+  let marketplace: Marketplace;
+
+  beforeEach(async () => {
+    marketplace = await setupMarketplace();
+  });
+
+  async function manageRole(action: "grantRole" | "revokeRole", role: object, holder: Keypair, admin = marketplace.admin) {
+    await marketplace.program.methods[action](role as any, holder.publicKey)
+      .accountsPartial({
+        marketplaceConfig: configPda(marketplace.program),
+        roleRegistry: roleRegistryPda(marketplace.program),
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  const fetchConfig = () => marketplace.program.account.marketplaceConfig.fetch(configPda(marketplace.program));
+
+  it("grants and revokes the verifier role", async () => {
+    const verifier = await fundedKeypair(marketplace);
+    const owner = await createUser(marketplace);
+
+    await expectError(verifyAsset(marketplace, await registerAsset(marketplace, owner), verifier), "Unauthorized");
+    await expectError(manageRole("grantRole", { verifier: {} }, verifier, verifier), "Unauthorized");
+
+    await manageRole("grantRole", { verifier: {} }, verifier);
+    await expectError(manageRole("grantRole", { verifier: {} }, verifier), "RoleAlreadyGranted");
+    await verifyAsset(marketplace, await registerAsset(marketplace, owner), verifier);
+
+    await manageRole("revokeRole", { verifier: {} }, verifier);
+    await expectError(manageRole("revokeRole", { verifier: {} }, verifier), "RoleNotGranted");
+    await expectError(verifyAsset(marketplace, await registerAsset(marketplace, owner), verifier), "Unauthorized");
+  });
+
+  it("lets a pauser pause without touching admin-only settings", async () => {
+    const pauser = await fundedKeypair(marketplace);
+    await expectError(updateConfig(marketplace, { pausedStatus: true }, pauser), "Unauthorized");
+
+    await manageRole("grantRole", { pauser: {} }, pauser);
+    await updateConfig(marketplace, { pausedStatus: true }, pauser);
+    expect((await fetchConfig()).isPaused).to.be.true;
+
+    await expectError(updateConfig(marketplace, { antiSnipeWindowSeconds: 30 }, pauser), "Unauthorized");
+  });
+
+  it("hands the admin seat over through a governance proposal", async () => {
+    const newAdmin = await fundedKeypair(marketplace);
+    const proposal = await proposeConfigChange(marketplace, { admin: { proposedAdmin: newAdmin.publicKey } });
+    await warpBy(marketplace, CONFIG_CHANGE_DELAY);
+    await executeConfigChange(marketplace, proposal);
+    expect((await fetchConfig()).pendingAdmin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+
+    const acceptAdmin = (signer: Keypair) =>
+      marketplace.program.methods
+        .acceptAdmin()
+        .accountsPartial({
+          marketplaceConfig: configPda(marketplace.program),
+          roleRegistry: roleRegistryPda(marketplace.program),
+          pendingAdmin: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    await expectError(acceptAdmin(marketplace.admin), "Unauthorized");
+    await acceptAdmin(newAdmin);
+
+    const config = await fetchConfig();
+    expect(config.admin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+    expect(config.pendingAdmin).to.be.null;
+
+    // The old key keeps neither its roles nor its signer seat.
+    expect(config.configSigners.map((signer) => signer.toBase58())).to.deep.equal([newAdmin.publicKey.toBase58()]);
+    const { assignments } = await marketplace.program.account.roleRegistry.fetch(roleRegistryPda(marketplace.program));
+    expect(assignments).to.have.length(5);
+    expect(assignments.every((assignment) => assignment.holder.equals(newAdmin.publicKey))).to.be.true;
+    await expectError(updateConfig(marketplace, { pausedStatus: true }, marketplace.admin), "Unauthorized");
+    await expectError(
+      proposeConfigChange(marketplace, { keeperFee: { keeperFeeBps: 1_000 } }, marketplace.admin),
+      "Unauthorized"
+    );
+  });
+
+  it("opens the same timelocked Admin proposal through propose_admin", async () => {
+    const newAdmin = await fundedKeypair(marketplace);
+    const proposeAdmin = async (proposer: Keypair) => {
+      const proposal = configProposalPda(marketplace.program, (await fetchConfig()).configProposalCount.toNumber());
+      await marketplace.program.methods
+        .proposeAdmin(newAdmin.publicKey)
+        .accountsPartial({
+          configProposal: proposal,
+          marketplaceConfig: configPda(marketplace.program),
+          roleRegistry: roleRegistryPda(marketplace.program),
+          proposer: proposer.publicKey,
+        })
+        .signers([proposer])
+        .rpc();
+      return proposal;
+    };
+
+    await expectError(proposeAdmin(newAdmin), "NotConfigSigner");
+    const proposal = await proposeAdmin(marketplace.admin);
+    const { change } = await marketplace.program.account.configProposal.fetch(proposal);
+    expect(change.admin.proposedAdmin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+
+    await expectError(executeConfigChange(marketplace, proposal), "ConfigChangeNotYetEffective");
+    await warpBy(marketplace, CONFIG_CHANGE_DELAY);
+    await executeConfigChange(marketplace, proposal);
+    expect((await fetchConfig()).pendingAdmin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+  });
+
+  it("lets the admin withdraw a pending handover", async () => {
+    const newAdmin = await fundedKeypair(marketplace);
+    const proposal = await proposeConfigChange(marketplace, { admin: { proposedAdmin: newAdmin.publicKey } });
+    await warpBy(marketplace, CONFIG_CHANGE_DELAY);
+    await executeConfigChange(marketplace, proposal);
+
+    await marketplace.program.methods
+      .cancelAdminTransfer()
+      .accountsPartial({ marketplaceConfig: configPda(marketplace.program), admin: marketplace.admin.publicKey })
+      .signers([marketplace.admin])
+      .rpc();
+    expect((await fetchConfig()).pendingAdmin).to.be.null;
+  });
+});
@@ -0,0 +1,190 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { randomBytes } from "crypto";
+import {
+  LISTING_FEE,
+  Marketplace,
+  User,
+  accountExists,
+  bidRecordPda,
+  configPda,
+  createUser,
+  createVerifiedAsset,
+  escrowAuthorityPda,
+  escrowPda,
+  expectError,
+  finalizeAuction,
+  listingAccounts,
+  sealedBidCommitment,
+  settleAuction,
+  setupMarketplace,
+  tokenBalance,
+  updateConfig,
+  warpTo,
+} from "./helpers";
+
+const COMMIT_DURATION = 60;
+const REVEAL_DURATION = 60;
+const DEPOSIT = 500;
+
+describe("sealed-bid auctions", () => {
+  let marketplace: Marketplace;
+  let seller: User;
+  let alice: User;
+  let bob: User;
+  let carol: User;
+  let asset: PublicKey;
+
+  beforeEach(async () => {
+    marketplace = await setupMarketplace();
+    await updateConfig(marketplace, { sealedBidSlashBps: 1_000 });
+    seller = await createUser(marketplace, 10_000);
+    alice = await createUser(marketplace, 10_000);
+    bob = await createUser(marketplace, 10_000);
+    carol = await createUser(marketplace, 10_000);
+    asset = await createVerifiedAsset(marketplace, seller);
+  });
+
+  async function listSealedAuction(minBid: number, isSecondPrice: boolean) {
+    const { auction, accounts } = await listingAccounts(marketplace, seller, asset);
+    await marketplace.program.methods
+      .listAssetForSealedAuction(
+        new BN(minBid),
+        new BN(COMMIT_DURATION),
+        new BN(REVEAL_DURATION),
+        new BN(DEPOSIT),
+        null,
+        null,
+        isSecondPrice
+      )
+      .accountsPartial(accounts)
+      .signers([seller.keypair])
+      .rpc();
+    return auction;
+  }
+
+  async function commit(auction: PublicKey, bidder: User, amount: number): Promise<Buffer> {
+    const salt = randomBytes(32);
+    await marketplace.program.methods
+      .commitSealedBid(sealedBidCommitment(auction, bidder.publicKey, amount, salt))
+      .accountsPartial({
+        auctionAccount: auction,
+        bidRecord: bidRecordPda(marketplace.program, auction, bidder.publicKey),
+        bidder: bidder.publicKey,
+        bidderPernTokenAccount: bidder.tokenAccount,
+        auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+      })
+      .signers([bidder.keypair])
+      .rpc();
+    return salt;
+  }
+
+  async function reveal(auction: PublicKey, bidder: User, amount: number, salt: Buffer) {
+    await marketplace.program.methods
+      .revealSealedBid(new BN(amount), Array.from(salt))
+      .accountsPartial({
+        auctionAccount: auction,
+        bidRecord: bidRecordPda(marketplace.program, auction, bidder.publicKey),
+        bidder: bidder.publicKey,
+        bidderPernTokenAccount: bidder.tokenAccount,
+        auctionEscrowTokenAccount: escrowPda(marketplace.program, auction),
+      })
+      .signers([bidder.keypair])
+      .rpc();
+  }
+
+  async function withdraw(auction: PublicKey, bidder: User) {
+    const escrow = escrowPda(marketplace.program, auction);
+    await marketplace.program.methods
+      .withdrawSealedBid()
+      .accountsPartial({
+        auctionAccount: auction,
+        bidRecord: bidRecordPda(marketplace.program, auction, bidder.publicKey),
+        bidder: bidder.publicKey,
+        signer: marketplace.provider.wallet.publicKey,
+        bidderPernTokenAccount: bidder.tokenAccount,
+        marketplaceConfig: configPda(marketplace.program),
+        treasuryPernTokenAccount: marketplace.treasury,
+        auctionEscrowTokenAccount: (await accountExists(marketplace, escrow)) ? escrow : null,
+        auctionEscrowAuthority: escrowAuthorityPda(marketplace.program, auction),
+        sellerRentRecipient: seller.publicKey,
+      })
+      .rpc();
+  }
+
+  it("settles a second-price auction, refunds revealed losers and slashes unrevealed bids", async () => {
+    const auction = await listSealedAuction(1_000, true);
+    const { auctionEndTimestamp, revealEndTimestamp } = await marketplace.program.account.auctionAccount.fetch(auction);
+
+    const aliceSalt = await commit(auction, alice, 3_000);
+    const bobSalt = await commit(auction, bob, 2_000);
+    await commit(auction, carol, 4_000);
+    expect(await tokenBalance(marketplace, escrowPda(marketplace.program, auction))).to.equal(3 * DEPOSIT);
+
+    await expectError(reveal(auction, alice, 3_000, aliceSalt), "RevealPhaseNotActive");
+    await warpTo(marketplace, auctionEndTimestamp.toNumber());
+    await expectError(reveal(auction, bob, 2_500, bobSalt), "InvalidBidReveal");
+    await reveal(auction, alice, 3_000, aliceSalt);
+    await reveal(auction, bob, 2_000, bobSalt);
+
+    await expectError(finalizeAuction(marketplace, auction), "AuctionNotEnded");
+    await warpTo(marketplace, revealEndTimestamp.toNumber());
+    await finalizeAuction(marketplace, auction);
+
+    let auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.highestBidder.toBase58()).to.equal(alice.publicKey.toBase58());
+    expect(auctionAccount.clearingPriceUsdStar.toNumber()).to.equal(2_000);
+
+    // The winner pays the runner-up's bid and gets the rest of their escrow back.
+    await settleAuction(marketplace, auction, seller, alice);
+    expect(await tokenBalance(marketplace, alice.tokenAccount)).to.equal(8_000);
+    expect(await tokenBalance(marketplace, seller.tokenAccount)).to.equal(10_000 - LISTING_FEE + 1_900);
+
+    await withdraw(auction, bob);
+    expect(await tokenBalance(marketplace, bob.tokenAccount)).to.equal(10_000);
+
+    await withdraw(auction, carol);
+    expect(await tokenBalance(marketplace, carol.tokenAccount)).to.equal(10_000 - 50);
+    expect(await tokenBalance(marketplace, marketplace.treasury)).to.equal(100 + 50);
+    expect(await accountExists(marketplace, escrowPda(marketplace.program, auction))).to.be.false;
+
+    // The winner's empty record is closed even though the escrow is already gone.
+    await withdraw(auction, alice);
+    expect(await accountExists(marketplace, bidRecordPda(marketplace.program, auction, alice.publicKey))).to.be.false;
+    auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ completed: {} });
+  });
+
+  it("charges the winning bid in a first-price auction", async () => {
+    const auction = await listSealedAuction(1_000, false);
+    const { auctionEndTimestamp, revealEndTimestamp } = await marketplace.program.account.auctionAccount.fetch(auction);
+
+    const aliceSalt = await commit(auction, alice, 3_000);
+    const bobSalt = await commit(auction, bob, 2_000);
+    await warpTo(marketplace, auctionEndTimestamp.toNumber());
+    await reveal(auction, alice, 3_000, aliceSalt);
+    await reveal(auction, bob, 2_000, bobSalt);
+    await warpTo(marketplace, revealEndTimestamp.toNumber());
+    await finalizeAuction(marketplace, auction);
+    await settleAuction(marketplace, auction, seller, alice);
+
+    expect(await tokenBalance(marketplace, alice.tokenAccount)).to.equal(7_000);
+  });
+
+  it("refunds every deposit when nobody reaches the minimum bid", async () => {
+    const auction = await listSealedAuction(5_000, false);
+    const { auctionEndTimestamp, revealEndTimestamp } = await marketplace.program.account.auctionAccount.fetch(auction);
+
+    const aliceSalt = await commit(auction, alice, 3_000);
+    await warpTo(marketplace, auctionEndTimestamp.toNumber());
+    await reveal(auction, alice, 3_000, aliceSalt);
+    await warpTo(marketplace, revealEndTimestamp.toNumber());
+    await finalizeAuction(marketplace, auction);
+
+    const auctionAccount = await marketplace.program.account.auctionAccount.fetch(auction);
+    expect(auctionAccount.auctionStatus).to.deep.equal({ endedUnsold: {} });
+    await withdraw(auction, alice);
+    expect(await tokenBalance(marketplace, alice.tokenAccount)).to.equal(10_000);
+  });
+});